@@ -1,7 +1,8 @@
+use crate::tip::TipStrategy;
 use crate::types::JitoError;
 use crate::{ArbitrageConfig, ArbitrageOpportunity, Jito, tool};
-use solana_sdk::{pubkey::Pubkey, signature::Keypair};
-use std::{str::FromStr, sync::Arc};
+use solana_sdk::{pubkey::Pubkey, signer::Signer};
+use std::sync::Arc;
 use tokio::time::{Duration, sleep};
 
 pub struct Arbitrage {
@@ -16,35 +17,44 @@ impl Arbitrage {
     }
     pub async fn exe_arbitrage(
         &self,
-        wallet: &Keypair,
+        wallet: &dyn Signer,
         opportunity: &ArbitrageOpportunity,
         config: &ArbitrageConfig,
-    ) -> Result<String, JitoError<String>> {
+    ) -> Result<String, JitoError> {
         self.jito.health_check().await?;
-        let tip_account = self.jito.get_optimal_tip_account().await?;
-        let tip_pubkey = Pubkey::from_str(&tip_account.pubkey)
-            .map_err(|e| JitoError::SerializationError(e.to_string()))?;
         let tip_amount = (opportunity.expected_profit as f64 * config.tip_percentage) as u64;
+        let tip_pubkey = self
+            .jito
+            .resolve_tip_account(&TipStrategy::new(tip_amount))
+            .await?;
         let arbitrage_txs = tool::build_arbitrage_transactions(
             self.jito.clone(),
             wallet,
             opportunity,
             tip_pubkey,
             tip_amount,
+            config.compute_unit_limit,
+            config.priority_fee,
         )
         .await?;
+        self.jito.validate_bundle(&arbitrage_txs).await?;
+        let retry = self
+            .jito
+            .config()
+            .retry
+            .clone()
+            .with_max_retries(config.max_retries);
         let bundle_id = self
             .jito
             .bundle
-            .send_bundle(arbitrage_txs, Some(tip_pubkey), Some(tip_amount))
-            .await
-            .map_err(|e| JitoError::BundleError(e.to_string()))?;
+            .send_bundle_with_retry(arbitrage_txs, &retry)
+            .await?;
         Ok(bundle_id)
     }
 
     pub async fn monitor_and_arbitrage(
         &self,
-        wallet: Arc<Keypair>,
+        wallet: Arc<dyn Signer + Send + Sync>,
         config: ArbitrageConfig,
         monitored_pairs: Vec<(Pubkey, Pubkey)>,
     ) {
@@ -70,10 +80,10 @@ impl Arbitrage {
 
     async fn arbitrage_cycle(
         &self,
-        wallet: &Keypair,
+        wallet: &dyn Signer,
         config: &ArbitrageConfig,
         monitored_pairs: &[(Pubkey, Pubkey)],
-    ) -> Result<String, JitoError<String>> {
+    ) -> Result<String, JitoError> {
         // Scanning arbitrage opportunities
         let opportunities = self
             .scan_arbitrage_opportunities(monitored_pairs, 1_000_000)
@@ -90,11 +100,12 @@ impl Arbitrage {
         self.exe_arbitrage(wallet, &best_opportunity, config).await
     }
 
+    #[allow(unreachable_code, unused_variables, unused_mut)]
     async fn scan_arbitrage_opportunities(
         &self,
         token_pairs: &[(Pubkey, Pubkey)],
         amount: u64,
-    ) -> Result<Vec<ArbitrageOpportunity>, JitoError<String>> {
+    ) -> Result<Vec<ArbitrageOpportunity>, JitoError> {
         let mut opportunities = Vec::new();
         for (input_mint, output_mint) in token_pairs {
             todo!();
@@ -0,0 +1,400 @@
+use solana_sdk::hash::Hash;
+use solana_sdk::message::{AddressLookupTableAccount, Instruction, Message, VersionedMessage};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::VersionedTransaction;
+
+use crate::bundle::{Bundle, compile_v0_message, with_compute_budget};
+use crate::global::MAX_COMPUTE_UNIT_LIMIT;
+use crate::remote_signer::AsyncSigner;
+use crate::signing::{missing_signers, partial_sign, partial_sign_async, sign_message};
+use crate::simulation::{SimulateBundleOptions, SimulationReport};
+use crate::tip::{TipPlacement, TipStrategy};
+use crate::types::JitoError;
+use crate::validation::BundleViolation;
+
+/// One transaction of a bundle under construction
+enum BundleItem<'a> {
+    /// compiled and signed by the builder
+    Instructions {
+        instructions: Vec<Instruction>,
+        /// signers of this transaction only, besides the builder's signers
+        signers: Vec<&'a dyn Signer>,
+    },
+    /// prebuilt, possibly partially signed; missing signatures are added
+    /// by the builder's signers
+    Transaction(VersionedTransaction),
+}
+
+/// Composes a bundle from instruction groups and prebuilt transactions.
+/// Each instruction group becomes one transaction, signed by the builder's
+/// signers with a shared blockhash; the tip and compute budget are added
+/// according to the builder settings, which default to `BundleConfig`;
+/// see `with_compute_budget` for groups with their own compute budget.
+/// With a compute unit margin, the bundle is simulated once to size the
+/// compute unit limit of each instruction group.
+///
+/// Every transaction is signed by the signers it requires among the builder
+/// signers and its own. Building fails with `JitoError::InvalidBundle`
+/// listing the missing signatures unless partial signing is allowed.
+pub struct BundleBuilder<'a> {
+    bundle: &'a Bundle,
+    payer: Option<Pubkey>,
+    signers: Vec<&'a dyn Signer>,
+    async_signers: Vec<&'a dyn AsyncSigner>,
+    items: Vec<BundleItem<'a>>,
+    partial_signing: bool,
+    compute_unit_limit: Option<u32>,
+    compute_unit_price: Option<u64>,
+    compute_unit_margin: Option<f64>,
+    check_balance: bool,
+    tip: Option<TipStrategy>,
+    lookup_tables: Vec<AddressLookupTableAccount>,
+    recent_blockhash: Option<Hash>,
+}
+
+impl<'a> BundleBuilder<'a> {
+    pub fn new(bundle: &'a Bundle) -> Self {
+        Self {
+            bundle,
+            payer: None,
+            signers: Vec::new(),
+            async_signers: Vec::new(),
+            items: Vec::new(),
+            partial_signing: false,
+            compute_unit_limit: bundle.config().compute_unit_limit,
+            compute_unit_price: bundle.config().priority_fee,
+            compute_unit_margin: bundle.config().compute_unit_margin,
+            check_balance: bundle.config().check_balance,
+            tip: bundle.config().tip.clone(),
+            lookup_tables: Vec::new(),
+            recent_blockhash: None,
+        }
+    }
+
+    /// fee payer and tip payer, the first signer when not set
+    pub fn with_payer(mut self, payer: Pubkey) -> Self {
+        self.payer = Some(payer);
+        self
+    }
+
+    /// signer of every transaction that requires its signature
+    pub fn with_signer(mut self, signer: &'a dyn Signer) -> Self {
+        self.signers.push(signer);
+        self
+    }
+
+    pub fn with_signers(mut self, signers: impl IntoIterator<Item = &'a dyn Signer>) -> Self {
+        self.signers.extend(signers);
+        self
+    }
+
+    /// out-of-process signer (HSM, KMS, wallet service) of every transaction
+    /// that requires its signature
+    pub fn with_async_signer(mut self, signer: &'a dyn AsyncSigner) -> Self {
+        self.async_signers.push(signer);
+        self
+    }
+
+    /// leave signatures of absent signers empty instead of failing, e.g.
+    /// for transactions a remote party signs later; see `missing_signers`
+    pub fn with_partial_signing(mut self, partial_signing: bool) -> Self {
+        self.partial_signing = partial_signing;
+        self
+    }
+
+    /// append a transaction made of `instructions`
+    pub fn add_instructions(self, instructions: Vec<Instruction>) -> Self {
+        self.add_instructions_with_signers(instructions, [])
+    }
+
+    /// append a transaction made of `instructions`, additionally signed by
+    /// `signers` (e.g. a token authority or multisig members)
+    pub fn add_instructions_with_signers(
+        mut self,
+        instructions: Vec<Instruction>,
+        signers: impl IntoIterator<Item = &'a dyn Signer>,
+    ) -> Self {
+        self.items.push(BundleItem::Instructions {
+            instructions,
+            signers: signers.into_iter().collect(),
+        });
+        self
+    }
+
+    /// append one transaction per instruction group
+    pub fn add_instruction_groups(mut self, instruction_groups: Vec<Vec<Instruction>>) -> Self {
+        for instructions in instruction_groups {
+            self = self.add_instructions(instructions);
+        }
+        self
+    }
+
+    /// append a signed or partially signed transaction
+    pub fn add_transaction<T: Into<VersionedTransaction>>(mut self, transaction: T) -> Self {
+        self.items.push(BundleItem::Transaction(transaction.into()));
+        self
+    }
+
+    pub fn add_transactions<T: Into<VersionedTransaction>>(
+        mut self,
+        transactions: impl IntoIterator<Item = T>,
+    ) -> Self {
+        self.items.extend(
+            transactions
+                .into_iter()
+                .map(|transaction| BundleItem::Transaction(transaction.into())),
+        );
+        self
+    }
+
+    /// `SetComputeUnitLimit` for every instruction group
+    pub fn with_compute_unit_limit(mut self, units: u32) -> Self {
+        self.compute_unit_limit = Some(units);
+        self
+    }
+
+    /// `SetComputeUnitPrice` (micro-lamports per CU) for every instruction group
+    pub fn with_compute_unit_price(mut self, micro_lamports: u64) -> Self {
+        self.compute_unit_price = Some(micro_lamports);
+        self
+    }
+
+    /// Size the `SetComputeUnitLimit` of every instruction group from a
+    /// simulation of the bundle, plus `margin` (e.g. `0.1` for 10%).
+    /// Overrides `with_compute_unit_limit`; a failed simulation fails the build.
+    pub fn with_compute_unit_margin(mut self, margin: f64) -> Self {
+        self.compute_unit_margin = Some(margin);
+        self
+    }
+
+    /// fail with `JitoError::InsufficientBalance` when the payer cannot cover
+    /// the tip and fees, see `Jito::check_payer_balance`
+    pub fn with_balance_check(mut self, check_balance: bool) -> Self {
+        self.check_balance = check_balance;
+        self
+    }
+
+    /// overrides `BundleConfig::tip`
+    pub fn with_tip(mut self, tip: Option<TipStrategy>) -> Self {
+        self.tip = tip;
+        self
+    }
+
+    /// compile v0 messages against these lookup tables
+    pub fn with_lookup_tables(mut self, lookup_tables: Vec<AddressLookupTableAccount>) -> Self {
+        self.lookup_tables = lookup_tables;
+        self
+    }
+
+    /// blockhash of the built transactions; defaults to the blockhash of the
+    /// last prebuilt transaction, otherwise the latest blockhash
+    pub fn with_recent_blockhash(mut self, recent_blockhash: Hash) -> Self {
+        self.recent_blockhash = Some(recent_blockhash);
+        self
+    }
+
+    /// build and sign the bundle without sending it
+    pub async fn build(mut self) -> Result<Vec<VersionedTransaction>, JitoError> {
+        let payer = self
+            .payer
+            .or_else(|| self.signers.first().map(|signer| signer.pubkey()))
+            .or_else(|| self.async_signers.first().map(|signer| signer.pubkey()));
+        let recent_blockhash = match self
+            .recent_blockhash
+            .or_else(|| self.last_prebuilt_blockhash())
+        {
+            Some(recent_blockhash) => recent_blockhash,
+            None => self.bundle.latest_blockhash().await?,
+        };
+        let mut items = std::mem::take(&mut self.items);
+        let mut tip_group = None;
+        if let Some(tip) = &self.tip {
+            let payer = payer.ok_or_else(no_payer_error)?;
+            let tip_account = self.bundle.jito().resolve_tip_account(tip).await?;
+            let tip_instruction = tip.instruction(&payer, &tip_account);
+            match (tip.placement, items.last_mut()) {
+                (
+                    TipPlacement::LastTransaction,
+                    Some(BundleItem::Instructions { instructions, .. }),
+                ) => instructions.push(tip_instruction),
+                _ => tip_group = Some(vec![tip_instruction]),
+            }
+        }
+        let mut unit_limits = vec![self.compute_unit_limit; items.len()];
+        if let Some(margin) = self.compute_unit_margin {
+            let draft_limits = vec![Some(MAX_COMPUTE_UNIT_LIMIT); items.len()];
+            let draft = self.assemble(
+                payer,
+                &items,
+                tip_group.as_deref(),
+                &draft_limits,
+                recent_blockhash,
+            )?;
+            unit_limits = self.size_compute_units(draft, items.len(), margin).await?;
+        }
+        let mut transactions = self.assemble(
+            payer,
+            &items,
+            tip_group.as_deref(),
+            &unit_limits,
+            recent_blockhash,
+        )?;
+        if self.check_balance
+            && let Some(payer) = payer
+        {
+            let tip_lamports = self.tip.as_ref().map_or(0, |tip| tip.lamports);
+            self.bundle
+                .jito()
+                .check_payer_balance(&payer, &transactions, tip_lamports)
+                .await?;
+        }
+        if !self.async_signers.is_empty() {
+            for transaction in &mut transactions {
+                if !missing_signers(transaction).is_empty() {
+                    partial_sign_async(transaction, &self.async_signers).await?;
+                }
+            }
+        }
+        if !self.partial_signing {
+            let violations: Vec<BundleViolation> = transactions
+                .iter()
+                .enumerate()
+                .filter_map(|(index, transaction)| {
+                    let signers = missing_signers(transaction);
+                    (!signers.is_empty())
+                        .then_some(BundleViolation::MissingSignatures { index, signers })
+                })
+                .collect();
+            if !violations.is_empty() {
+                return Err(JitoError::InvalidBundle(violations));
+            }
+        }
+        Ok(transactions)
+    }
+
+    /// build the bundle and simulate it without sending
+    pub async fn simulate(
+        self,
+        options: &SimulateBundleOptions,
+    ) -> Result<SimulationReport, JitoError> {
+        let bundle = self.bundle;
+        let transactions = self.build().await?;
+        bundle.simulate_bundle(transactions, options).await
+    }
+
+    /// build the bundle and send it with `Bundle::send_bundle`
+    pub async fn send(self) -> Result<String, JitoError> {
+        let bundle = self.bundle;
+        let transactions = self.build().await?;
+        bundle.send_bundle(transactions).await
+    }
+
+    fn last_prebuilt_blockhash(&self) -> Option<Hash> {
+        self.items.iter().rev().find_map(|item| match item {
+            BundleItem::Transaction(transaction) => Some(*transaction.message.recent_blockhash()),
+            BundleItem::Instructions { .. } => None,
+        })
+    }
+
+    /// sign every item plus the standalone tip; `unit_limits` holds the
+    /// compute unit limit of each item
+    fn assemble(
+        &self,
+        payer: Option<Pubkey>,
+        items: &[BundleItem<'a>],
+        tip_group: Option<&[Instruction]>,
+        unit_limits: &[Option<u32>],
+        recent_blockhash: Hash,
+    ) -> Result<Vec<VersionedTransaction>, JitoError> {
+        let mut transactions = Vec::with_capacity(items.len() + 1);
+        for (item, unit_limit) in items.iter().zip(unit_limits) {
+            transactions.push(match item {
+                BundleItem::Transaction(transaction) => {
+                    let mut transaction = transaction.clone();
+                    if !missing_signers(&transaction).is_empty() {
+                        partial_sign(&mut transaction, &self.signers)?;
+                    }
+                    transaction
+                }
+                BundleItem::Instructions {
+                    instructions,
+                    signers,
+                } => {
+                    let payer = payer.ok_or_else(no_payer_error)?;
+                    let instructions = with_compute_budget(
+                        instructions.clone(),
+                        *unit_limit,
+                        self.compute_unit_price,
+                    );
+                    self.sign(&payer, &instructions, signers, recent_blockhash)?
+                }
+            });
+        }
+        // the standalone tip needs no compute budget
+        if let Some(tip_group) = tip_group {
+            let payer = payer.ok_or_else(no_payer_error)?;
+            transactions.push(self.sign(&payer, tip_group, &[], recent_blockhash)?);
+        }
+        Ok(transactions)
+    }
+
+    /// Simulate a draft bundle and size the compute unit limit of the first
+    /// `items` transactions to the units consumed plus `margin`, capped at
+    /// `MAX_COMPUTE_UNIT_LIMIT`. The draft is not signed by the async
+    /// signers, so signatures are not verified.
+    async fn size_compute_units(
+        &self,
+        draft: Vec<VersionedTransaction>,
+        items: usize,
+        margin: f64,
+    ) -> Result<Vec<Option<u32>>, JitoError> {
+        let options = SimulateBundleOptions {
+            skip_sig_verify: true,
+            ..Default::default()
+        };
+        let report = self.bundle.simulate_bundle(draft, &options).await?;
+        if !report.is_success() {
+            return Err(JitoError::simulation_failed(report));
+        }
+        Ok((0..items)
+            .map(|index| {
+                report
+                    .transaction_results
+                    .get(index)
+                    .and_then(|result| result.units_consumed)
+                    .map(|units| {
+                        let units = (units as f64 * (1.0 + margin)).ceil() as u64;
+                        units.min(MAX_COMPUTE_UNIT_LIMIT as u64) as u32
+                    })
+                    .or(self.compute_unit_limit)
+            })
+            .collect())
+    }
+
+    /// compile a legacy message (v0 with lookup tables) and sign it with
+    /// the builder signers plus `signers`
+    fn sign(
+        &self,
+        payer: &Pubkey,
+        instructions: &[Instruction],
+        signers: &[&dyn Signer],
+        recent_blockhash: Hash,
+    ) -> Result<VersionedTransaction, JitoError> {
+        let message = if self.lookup_tables.is_empty() {
+            VersionedMessage::Legacy(Message::new_with_blockhash(
+                instructions,
+                Some(payer),
+                &recent_blockhash,
+            ))
+        } else {
+            compile_v0_message(payer, instructions, &self.lookup_tables, recent_blockhash)?
+        };
+        let signers: Vec<&dyn Signer> = signers.iter().chain(&self.signers).copied().collect();
+        sign_message(message, &signers)
+    }
+}
+
+fn no_payer_error() -> JitoError {
+    JitoError::Transaction("bundle builder has no payer or signer".to_string())
+}
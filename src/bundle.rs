@@ -1,242 +1,444 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use crate::Jito;
+use crate::builder::BundleBuilder;
+use crate::fanout::FanoutResult;
+use crate::retry::RetryPolicy;
+use crate::simulation::{SimulateBundleOptions, SimulationReport};
+use crate::tip::TipStrategy;
 use crate::types::JitoError;
+use solana_address_lookup_table_interface::state::AddressLookupTable;
+use solana_compute_budget_interface::ComputeBudgetInstruction;
 use solana_network_sdk::tool::token;
-use solana_program::example_mocks::solana_sdk::system_instruction;
-use solana_sdk::message::Instruction;
-use solana_sdk::{
-    message::Message, pubkey::Pubkey, signature::Keypair, signer::Signer, transaction::Transaction,
-};
+use solana_sdk::hash::Hash;
+use solana_sdk::message::{AddressLookupTableAccount, Instruction, VersionedMessage, v0};
+use solana_sdk::transaction::VersionedTransaction;
+use solana_sdk::{pubkey::Pubkey, signer::Signer};
+use solana_system_interface::instruction as system_instruction;
 
 pub struct Bundle {
     jito: Arc<Jito>,
+    config: BundleConfig,
 }
 impl Bundle {
     /// create a new Bundler
     pub fn new(jito: Jito) -> Self {
         Self {
             jito: Arc::new(jito),
+            config: BundleConfig::default(),
         }
     }
 
+    pub fn with_config(mut self, config: BundleConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn config(&self) -> &BundleConfig {
+        &self.config
+    }
+
+    /// start composing a bundle, see `BundleBuilder`
+    pub fn builder(&self) -> BundleBuilder<'_> {
+        BundleBuilder::new(self)
+    }
+
+    pub(crate) fn jito(&self) -> &Jito {
+        &self.jito
+    }
+
+    /// the client retry policy limited to `BundleConfig::max_retries`
+    fn retry_policy(&self) -> RetryPolicy {
+        self.jito
+            .config()
+            .retry
+            .clone()
+            .with_max_retries(self.config.max_retries)
+    }
+
     /// Simple bundled transaction functionality - send any transaction package
-    pub async fn send_bundle(
+    /// (legacy `Transaction` or `VersionedTransaction`) as-is, after
+    /// `Jito::validate_bundle` unless `BundleConfig::validate` is off
+    pub async fn send_bundle<T: Into<VersionedTransaction>>(
         &self,
-        transactions: Vec<Transaction>,
-        tip_account: Option<Pubkey>,
-        tip_amount: Option<u64>,
-    ) -> Result<String, JitoError<String>> {
+        transactions: Vec<T>,
+    ) -> Result<String, JitoError> {
+        let transactions: Vec<VersionedTransaction> =
+            transactions.into_iter().map(Into::into).collect();
+        if self.config.validate {
+            self.jito.validate_bundle(&transactions).await?;
+        }
         self.jito
             .bundle
-            .send_bundle(transactions, tip_account, tip_amount)
+            .send_bundle_with_retry(transactions, &self.retry_policy())
+            .await
+    }
+
+    /// Send the bundle to every fan-out region concurrently, see
+    /// `JitoConfig::with_fanout_regions`; validated like `send_bundle`
+    pub async fn send_bundle_fanout<T: Into<VersionedTransaction>>(
+        &self,
+        transactions: Vec<T>,
+    ) -> Result<FanoutResult, JitoError> {
+        let transactions: Vec<VersionedTransaction> =
+            transactions.into_iter().map(Into::into).collect();
+        if self.config.validate {
+            self.jito.validate_bundle(&transactions).await?;
+        }
+        self.jito
+            .fanout
+            .send_bundle_with_retry(transactions, &self.retry_policy())
+            .await
+    }
+
+    /// Hold the bundle until a Jito validator leads within `within_slots`
+    /// slots, then send it. Bundles only land in Jito leader slots, so
+    /// sending early just wastes the blockhash lifetime. Fails with
+    /// `JitoError::Bundle` when no Jito leader comes up within `timeout`.
+    pub async fn send_when_jito_leader_upcoming<T: Into<VersionedTransaction>>(
+        &self,
+        transactions: Vec<T>,
+        within_slots: u64,
+        timeout: Duration,
+    ) -> Result<String, JitoError> {
+        let Some(leader) = self
+            .jito
+            .wait_for_jito_leader(within_slots, timeout)
+            .await?
+        else {
+            return Err(JitoError::Bundle(format!(
+                "no Jito leader within {} slots after {:?}",
+                within_slots, timeout
+            )));
+        };
+        log::debug!("Jito leader {} at slot {}", leader.pubkey, leader.slot);
+        self.send_bundle(transactions).await
+    }
+
+    /// Simulate the bundle without submitting it (`simulateBundle`, or the
+    /// sequential `simulateTransaction` fallback without a Jito-enabled rpc)
+    pub async fn simulate_bundle<T: Into<VersionedTransaction>>(
+        &self,
+        transactions: Vec<T>,
+        options: &SimulateBundleOptions,
+    ) -> Result<SimulationReport, JitoError> {
+        let transactions: Vec<VersionedTransaction> =
+            transactions.into_iter().map(Into::into).collect();
+        self.jito.simulate_bundle(&transactions, options).await
+    }
+
+    /// Simulate the bundle first and only send it when every transaction
+    /// succeeds; a failed simulation returns `JitoError::SimulationFailed`
+    /// with the report attached and nothing (including the tip) is
+    /// submitted. `JitoError::is_blockhash_expired` tells whether the
+    /// blockhash expired.
+    pub async fn send_bundle_checked<T: Into<VersionedTransaction>>(
+        &self,
+        transactions: Vec<T>,
+        options: &SimulateBundleOptions,
+    ) -> Result<String, JitoError> {
+        let transactions: Vec<VersionedTransaction> =
+            transactions.into_iter().map(Into::into).collect();
+        let report = self.jito.simulate_bundle(&transactions, options).await?;
+        if !report.is_success() {
+            return Err(JitoError::simulation_failed(report));
+        }
+        self.send_bundle(transactions).await
+    }
+
+    /// Sign one transaction per instruction group with a fresh blockhash,
+    /// inject the tip according to the strategy and send the bundle
+    pub async fn send_instruction_bundle(
+        &self,
+        wallet: &dyn Signer,
+        instruction_groups: Vec<Vec<Instruction>>,
+        tip: Option<&TipStrategy>,
+    ) -> Result<String, JitoError> {
+        self.builder()
+            .with_signer(wallet)
+            .add_instruction_groups(instruction_groups)
+            .with_tip(tip.cloned())
+            .send()
             .await
-            .map_err(|e| JitoError::BundleError(e.to_string()))
+    }
+
+    pub(crate) async fn latest_blockhash(&self) -> Result<Hash, JitoError> {
+        Ok(self.jito.solana.client_arc().get_latest_blockhash().await?)
     }
 
     /// Create token transfer bundled transactions
+    #[allow(clippy::too_many_arguments, unused_variables)]
     pub async fn create_token_transfer_bundle(
         &self,
-        wallet: &Keypair,
+        wallet: &dyn Signer,
         token_mint: Pubkey,
         from_token_account: Pubkey,
         to_token_account: Pubkey,
         ui_amount: f64,
         decimals: u8,
-        tip_account: Option<Pubkey>,
-        tip_amount: Option<u64>,
-    ) -> Result<String, JitoError<String>> {
-        let raw_amount = token::safe_ui_to_raw_result(ui_amount, decimals)
-            .map_err(|e| JitoError::BundleError(e))?;
-        // Get the latest blockhash
-        let recent_blockhash = self
-            .jito
-            .solana
-            .client_arc()
-            .get_latest_blockhash()
-            .await
-            .map_err(|e| JitoError::BundleError(e.to_string()))?;
+        tip: Option<TipStrategy>,
+    ) -> Result<String, JitoError> {
+        let raw_amount =
+            token::safe_ui_to_raw_result(ui_amount, decimals).map_err(JitoError::Transaction)?;
         // Create token transfer instruction
-        let transfer_instruction = spl_token_interface::instruction::transfer(
-            &spl_token::id(),
+        let transfer_instruction = token_transfer_instruction(
             &from_token_account,
             &to_token_account,
             &wallet.pubkey(),
             &[],
             raw_amount,
-        )
-        .map_err(|e| JitoError::Error(format!("{:?}", e)))?;
-        let message = Message::new_with_blockhash(
-            &[transfer_instruction],
-            Some(&wallet.pubkey()),
-            &recent_blockhash,
-        );
-        let token_transfer_tx = Transaction::new(&[wallet], message, recent_blockhash);
+        )?;
         // Send bundled deal
-        self.send_bundle(vec![token_transfer_tx], tip_account, tip_amount)
+        self.send_instruction_bundle(wallet, vec![vec![transfer_instruction]], tip.as_ref())
             .await
     }
 
     /// Create SOL transfer bundled transaction
     pub async fn create_sol_transfer_bundle(
         &self,
-        wallet: &Keypair,
+        wallet: &dyn Signer,
         to_pubkey: Pubkey,
         lamports: u64,
-        tip_account: Option<Pubkey>,
-        tip_amount: Option<u64>,
-    ) -> Result<String, JitoError<String>> {
-        let recent_blockhash = self
-            .jito
-            .solana
-            .client_arc()
-            .get_latest_blockhash()
-            .await
-            .map_err(|e| JitoError::BundleError(e.to_string()))?;
+        tip: Option<TipStrategy>,
+    ) -> Result<String, JitoError> {
         // Create SOL transfer instruction
         let transfer_instruction =
             system_instruction::transfer(&wallet.pubkey(), &to_pubkey, lamports);
-        let message = Message::new_with_blockhash(
-            &[transfer_instruction],
-            Some(&wallet.pubkey()),
-            &recent_blockhash,
-        );
-        let sol_transfer_tx = Transaction::new(&[wallet], message, recent_blockhash);
-        self.send_bundle(vec![sol_transfer_tx], tip_account, tip_amount)
+        self.send_instruction_bundle(wallet, vec![vec![transfer_instruction]], tip.as_ref())
             .await
     }
 
     /// Create token trading bundles (e.g., trading on Raydium).
     pub async fn create_token_swap_bundle(
         &self,
-        wallet: &Keypair,
+        wallet: &dyn Signer,
         swap_instructions: Vec<Instruction>,
-        tip_account: Option<Pubkey>,
-        tip_amount: Option<u64>,
-    ) -> Result<String, JitoError<String>> {
-        let recent_blockhash = self
-            .jito
-            .solana
-            .client_arc()
-            .get_latest_blockhash()
+        tip: Option<TipStrategy>,
+    ) -> Result<String, JitoError> {
+        self.send_instruction_bundle(wallet, vec![swap_instructions], tip.as_ref())
             .await
-            .map_err(|e| JitoError::BundleError(e.to_string()))?;
-        let message = Message::new_with_blockhash(
-            &swap_instructions,
-            Some(&wallet.pubkey()),
-            &recent_blockhash,
-        );
-        let swap_tx = Transaction::new(&[wallet], message, recent_blockhash);
-        self.send_bundle(vec![swap_tx], tip_account, tip_amount)
+    }
+
+    /// Create token trading bundles with a v0 message resolving accounts
+    /// through address lookup tables (for routes exceeding the packet limit).
+    pub async fn create_v0_swap_bundle(
+        &self,
+        wallet: &dyn Signer,
+        swap_instructions: Vec<Instruction>,
+        lookup_tables: &[AddressLookupTableAccount],
+        tip: Option<TipStrategy>,
+    ) -> Result<String, JitoError> {
+        self.builder()
+            .with_signer(wallet)
+            .add_instructions(swap_instructions)
+            .with_lookup_tables(lookup_tables.to_vec())
+            .with_tip(tip)
+            .send()
             .await
     }
 
-    /// Creating complex multi-transaction bundles
-    pub async fn create_multi_transaction_bundle(
+    /// Load address lookup table accounts from chain
+    pub async fn get_lookup_tables(
         &self,
-        wallet: &Keypair,
-        transactions: Vec<Transaction>,
-        tip_account: Option<Pubkey>,
-        tip_amount: Option<u64>,
-    ) -> Result<String, JitoError<String>> {
-        self.send_bundle(transactions, tip_account, tip_amount)
+        addresses: &[Pubkey],
+    ) -> Result<Vec<AddressLookupTableAccount>, JitoError> {
+        let client = self.jito.solana.client_arc();
+        let mut lookup_tables = Vec::with_capacity(addresses.len());
+        for address in addresses {
+            let account = client.get_account(address).await?;
+            let table = AddressLookupTable::deserialize(&account.data)
+                .map_err(|e| JitoError::Serialization(format!("{:?}", e)))?;
+            lookup_tables.push(AddressLookupTableAccount {
+                key: *address,
+                addresses: table.addresses.to_vec(),
+            });
+        }
+        Ok(lookup_tables)
+    }
+
+    /// Creating complex multi-transaction bundles. Prebuilt transactions are
+    /// already signed, so the tip is always added as a standalone transaction
+    /// signed with the blockhash of the last transaction.
+    pub async fn create_multi_transaction_bundle<T: Into<VersionedTransaction>>(
+        &self,
+        wallet: &dyn Signer,
+        transactions: Vec<T>,
+        tip: Option<TipStrategy>,
+    ) -> Result<String, JitoError> {
+        self.builder()
+            .with_signer(wallet)
+            .add_transactions(transactions)
+            .with_tip(tip)
+            .send()
             .await
     }
 
     /// Create a bundled transaction of token transfer + tip
+    #[allow(unused_variables)]
     pub async fn create_token_transfer_with_tip_bundle(
         &self,
-        wallet: &Keypair,
+        wallet: &dyn Signer,
         token_mint: Pubkey,
         from_token_account: Pubkey,
         to_token_account: Pubkey,
         token_amount: u64,
-        tip_account: Pubkey,
-        tip_amount: u64,
-    ) -> Result<String, JitoError<String>> {
-        let recent_blockhash = self
-            .jito
-            .solana
-            .client_arc()
-            .get_latest_blockhash()
-            .await
-            .map_err(|e| JitoError::BundleError(e.to_string()))?;
-        let mut transactions = Vec::new();
+        tip: TipStrategy,
+    ) -> Result<String, JitoError> {
         // Token transfer transactions
-        let transfer_instruction = spl_token_interface::instruction::transfer(
-            &spl_token::id(),
+        let transfer_instruction = token_transfer_instruction(
             &from_token_account,
             &to_token_account,
             &wallet.pubkey(),
             &[],
             token_amount,
-        )
-        .map_err(|e| JitoError::Error(format!("{:?}", e)))?;
-        let transfer_message = Message::new_with_blockhash(
-            &[transfer_instruction],
-            Some(&wallet.pubkey()),
-            &recent_blockhash,
-        );
-        let transfer_tx = Transaction::new(&[wallet], transfer_message, recent_blockhash);
-        transactions.push(transfer_tx);
-        // Tip payment transaction
-        let tip_instruction =
-            system_instruction::transfer(&wallet.pubkey(), &tip_account, tip_amount);
-        let tip_message = Message::new_with_blockhash(
-            &[tip_instruction],
-            Some(&wallet.pubkey()),
-            &recent_blockhash,
-        );
-        let tip_tx = Transaction::new(&[wallet], tip_message, recent_blockhash);
-        transactions.push(tip_tx);
-        self.send_bundle(transactions, Some(tip_account), Some(tip_amount))
+        )?;
+        self.send_instruction_bundle(wallet, vec![vec![transfer_instruction]], Some(&tip))
             .await
     }
 
     /// Bulk token transfer bundling
     pub async fn create_batch_token_transfers_bundle(
         &self,
-        wallet: &Keypair,
+        wallet: &dyn Signer,
         transfers: Vec<TokenTransferRequest>,
-        tip_account: Option<Pubkey>,
-        tip_amount: Option<u64>,
-    ) -> Result<String, JitoError<String>> {
-        let recent_blockhash = self
-            .jito
-            .solana
-            .client_arc()
-            .get_latest_blockhash()
+        tip: Option<TipStrategy>,
+    ) -> Result<String, JitoError> {
+        self.create_batch_token_transfers_bundle_with_signers(wallet, &[], transfers, tip)
             .await
-            .map_err(|e| JitoError::BundleError(e.to_string()))?;
-        let mut transactions = Vec::new();
-        for transfer in transfers {
-            let transfer_instruction = spl_token_interface::instruction::transfer(
-                &spl_token::id(),
-                &transfer.from_token_account,
-                &transfer.to_token_account,
-                &wallet.pubkey(),
-                &[],
-                transfer.amount,
-            )
-            .map_err(|e| JitoError::Error(format!("{:?}", e)))?;
-            let message = Message::new_with_blockhash(
-                &[transfer_instruction],
-                Some(&wallet.pubkey()),
-                &recent_blockhash,
-            );
-            let tx = Transaction::new(&[wallet], message, recent_blockhash);
-            transactions.push(tx);
-        }
-        self.send_bundle(transactions, tip_account, tip_amount)
+    }
+
+    /// Bulk token transfer bundling with `wallet` as fee payer and `signers`
+    /// for transfer authorities other than the wallet (including multisig
+    /// members)
+    pub async fn create_batch_token_transfers_bundle_with_signers(
+        &self,
+        wallet: &dyn Signer,
+        signers: &[&dyn Signer],
+        transfers: Vec<TokenTransferRequest>,
+        tip: Option<TipStrategy>,
+    ) -> Result<String, JitoError> {
+        let instruction_groups = transfers
+            .iter()
+            .map(|transfer| {
+                let multisig_signers: Vec<&Pubkey> = transfer.multisig_signers.iter().collect();
+                token_transfer_instruction(
+                    &transfer.from_token_account,
+                    &transfer.to_token_account,
+                    &transfer.authority.unwrap_or_else(|| wallet.pubkey()),
+                    &multisig_signers,
+                    transfer.amount,
+                )
+                .map(|instruction| vec![instruction])
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        self.builder()
+            .with_signer(wallet)
+            .with_signers(signers.iter().copied())
+            .add_instruction_groups(instruction_groups)
+            .with_tip(tip)
+            .send()
             .await
     }
 }
 
+/// SPL token transfer instruction; `multisig_signers` sign for a multisig
+/// `authority`
+pub fn token_transfer_instruction(
+    from_token_account: &Pubkey,
+    to_token_account: &Pubkey,
+    authority: &Pubkey,
+    multisig_signers: &[&Pubkey],
+    amount: u64,
+) -> Result<Instruction, JitoError> {
+    spl_token_interface::instruction::transfer(
+        &spl_token::id(),
+        from_token_account,
+        to_token_account,
+        authority,
+        multisig_signers,
+        amount,
+    )
+    .map_err(|e| JitoError::Transaction(format!("{:?}", e)))
+}
+
+/// `SetComputeUnitLimit` / `SetComputeUnitPrice` instructions to prepend to
+/// a transaction; empty when neither is set
+pub fn compute_budget_instructions(
+    unit_limit: Option<u32>,
+    unit_price: Option<u64>,
+) -> Vec<Instruction> {
+    unit_limit
+        .map(ComputeBudgetInstruction::set_compute_unit_limit)
+        .into_iter()
+        .chain(unit_price.map(ComputeBudgetInstruction::set_compute_unit_price))
+        .collect()
+}
+
+/// `ComputeBudgetInstruction::SetComputeUnitLimit` discriminant
+const SET_COMPUTE_UNIT_LIMIT_TAG: u8 = 2;
+/// `ComputeBudgetInstruction::SetComputeUnitPrice` discriminant
+const SET_COMPUTE_UNIT_PRICE_TAG: u8 = 3;
+
+/// Prepend the compute budget to `instructions`. Compute budget
+/// instructions of the same kind already in the group (e.g. from a swap
+/// aggregator) are replaced, since the runtime rejects duplicates.
+pub fn with_compute_budget(
+    instructions: Vec<Instruction>,
+    unit_limit: Option<u32>,
+    unit_price: Option<u64>,
+) -> Vec<Instruction> {
+    let replaced = |instruction: &Instruction| {
+        solana_compute_budget_interface::check_id(&instruction.program_id)
+            && match instruction.data.first() {
+                Some(&SET_COMPUTE_UNIT_LIMIT_TAG) => unit_limit.is_some(),
+                Some(&SET_COMPUTE_UNIT_PRICE_TAG) => unit_price.is_some(),
+                _ => false,
+            }
+    };
+    let mut with_budget = compute_budget_instructions(unit_limit, unit_price);
+    with_budget.extend(
+        instructions
+            .into_iter()
+            .filter(|instruction| !replaced(instruction)),
+    );
+    with_budget
+}
+
+/// Compile a v0 message from instructions plus address lookup tables.
+pub fn compile_v0_message(
+    payer: &Pubkey,
+    instructions: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+    recent_blockhash: Hash,
+) -> Result<VersionedMessage, JitoError> {
+    let message = v0::Message::try_compile(payer, instructions, lookup_tables, recent_blockhash)
+        .map_err(|e| JitoError::Transaction(format!("{:?}", e)))?;
+    Ok(VersionedMessage::V0(message))
+}
+
+/// Compile and sign a v0 transaction.
+pub fn build_v0_transaction(
+    signers: &[&dyn Signer],
+    payer: &Pubkey,
+    instructions: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+    recent_blockhash: Hash,
+) -> Result<VersionedTransaction, JitoError> {
+    let message = compile_v0_message(payer, instructions, lookup_tables, recent_blockhash)?;
+    VersionedTransaction::try_new(message, signers)
+        .map_err(|e| JitoError::Transaction(format!("{:?}", e)))
+}
+
 #[derive(Debug, Clone)]
 pub struct TokenTransferRequest {
     pub from_token_account: Pubkey,
     pub to_token_account: Pubkey,
     pub amount: u64,
+    /// owner or delegate of the source account, the wallet when `None`
+    pub authority: Option<Pubkey>,
+    /// signers of a multisig authority
+    pub multisig_signers: Vec<Pubkey>,
 }
 
 impl TokenTransferRequest {
@@ -245,25 +447,90 @@ impl TokenTransferRequest {
             from_token_account,
             to_token_account,
             amount,
+            authority: None,
+            multisig_signers: Vec::new(),
         }
     }
+
+    pub fn with_authority(mut self, authority: Pubkey) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    pub fn with_multisig_signers(mut self, multisig_signers: Vec<Pubkey>) -> Self {
+        self.multisig_signers = multisig_signers;
+        self
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct BundleConfig {
-    pub tip_account: Option<Pubkey>,
-    pub tip_amount: Option<u64>,
+    pub tip: Option<TipStrategy>,
+    /// compute unit price in micro-lamports of every built transaction
     pub priority_fee: Option<u64>,
+    /// compute unit limit of every built transaction
+    pub compute_unit_limit: Option<u32>,
+    /// size the compute unit limit of each built transaction from a
+    /// simulation, plus this fraction (e.g. `0.1` for 10%)
+    pub compute_unit_margin: Option<f64>,
     pub max_retries: u32,
+    /// validate bundles before sending them
+    pub validate: bool,
+    /// check that the payer covers the tip and fees of built bundles
+    pub check_balance: bool,
 }
 
 impl Default for BundleConfig {
     fn default() -> Self {
         Self {
-            tip_account: None,
-            tip_amount: None,
+            tip: None,
             priority_fee: Some(50_000),
+            compute_unit_limit: None,
+            compute_unit_margin: None,
             max_retries: 3,
+            validate: true,
+            check_balance: true,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use solana_compute_budget_interface::ComputeBudgetInstruction;
+
+    use super::*;
+
+    #[test]
+    fn compute_budget_replaces_existing_instructions_of_the_same_kind() {
+        let swap = Instruction::new_with_bytes(Pubkey::new_unique(), &[7], vec![]);
+        let instructions = vec![
+            ComputeBudgetInstruction::set_compute_unit_limit(300_000),
+            ComputeBudgetInstruction::set_compute_unit_price(1_000),
+            ComputeBudgetInstruction::request_heap_frame(64 * 1024),
+            swap.clone(),
+        ];
+        assert_eq!(
+            with_compute_budget(instructions.clone(), Some(200_000), Some(50_000)),
+            vec![
+                ComputeBudgetInstruction::set_compute_unit_limit(200_000),
+                ComputeBudgetInstruction::set_compute_unit_price(50_000),
+                ComputeBudgetInstruction::request_heap_frame(64 * 1024),
+                swap.clone(),
+            ]
+        );
+        // kinds the builder does not set are left to the group
+        assert_eq!(
+            with_compute_budget(instructions.clone(), None, Some(50_000)),
+            vec![
+                ComputeBudgetInstruction::set_compute_unit_price(50_000),
+                ComputeBudgetInstruction::set_compute_unit_limit(300_000),
+                ComputeBudgetInstruction::request_heap_frame(64 * 1024),
+                swap,
+            ]
+        );
+        assert_eq!(
+            with_compute_budget(instructions.clone(), None, None),
+            instructions
+        );
+    }
+}
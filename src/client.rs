@@ -1,10 +1,22 @@
 use crate::JitoError;
-use crate::global::BUNDLE_RPC;
-use crate::global::TRANSACTIONS_POOL_RPC;
-use base64::{Engine, prelude::BASE64_STANDARD};
-use reqwest::Client;
-use serde::{Deserialize, Serialize};
-use solana_sdk::{pubkey::Pubkey, transaction::Transaction};
+use crate::config::{JitoAuth, JitoConfig, Region};
+use crate::encoding::{TxEncoding, encode_transaction, encode_transactions};
+use crate::global::MAX_BUNDLE_IDS_PER_REQUEST;
+use crate::global::TRANSACTIONS_POOL_PATH;
+use crate::global::{AUTH_HEADER, BUNDLE_PATH};
+use crate::rate_limit::RateLimiter;
+use crate::retry::{Idempotency, RetryPolicy};
+use crate::routing::RegionRouter;
+use crate::types::{retry_after, rpc_code};
+use reqwest::header::{CONTENT_TYPE, HeaderValue};
+use reqwest::{Client, RequestBuilder, StatusCode};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use solana_sdk::transaction::VersionedTransaction;
+use std::fmt;
+use std::sync::{
+    Arc,
+    atomic::{AtomicU64, Ordering},
+};
 pub enum ClientEnum {
     Bundle,
     Tip,
@@ -17,288 +29,527 @@ pub enum ClientEnum {
 
 impl ClientEnum {}
 
-/// ============== bundle client ==============
+/// ============== json-rpc transport ==============
 
-#[derive(Debug, Clone)]
-pub struct BundleClient {
-    client: Client,
+#[derive(Debug, Serialize)]
+pub struct JsonRpcRequest<'a, P> {
+    pub jsonrpc: &'static str,
+    pub id: u64,
+    pub method: &'a str,
+    pub params: P,
 }
 
-#[derive(Debug, Serialize)]
-struct BundleRequest {
-    jsonrpc: String,
-    id: u64,
-    method: String,
-    params: Vec<BundleParams>,
+#[derive(Debug, Deserialize)]
+pub struct JsonRpcResponse<R> {
+    #[serde(default)]
+    pub id: Option<u64>,
+    pub result: Option<R>,
+    pub error: Option<JsonRpcError>,
 }
 
-#[derive(Debug, Serialize)]
-struct BundleParams {
-    txs: Vec<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    tip_account: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    tip_amount: Option<u64>,
+/// JSON-RPC 2.0 error object
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonRpcError {
+    pub code: i64,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
-struct BundleResponse {
-    result: Option<BundleResult>,
-    error: Option<JitoError<String>>,
+impl fmt::Display for JsonRpcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.code, self.message)?;
+        if let Some(data) = &self.data {
+            write!(f, " ({})", data)?;
+        }
+        Ok(())
+    }
 }
 
-#[derive(Debug, Deserialize)]
-struct BundleResult {
-    bundle_id: String,
+impl std::error::Error for JsonRpcError {}
+
+/// `{ context: { slot }, value }` wrapper used by status methods
+#[derive(Debug, Clone, Deserialize)]
+pub struct RpcResponseContext {
+    pub slot: u64,
 }
 
-impl BundleClient {
-    pub fn new() -> Self {
+#[derive(Debug, Clone, Deserialize)]
+pub struct RpcContextResponse<T> {
+    pub context: RpcResponseContext,
+    pub value: T,
+}
+
+/// JSON-RPC transport shared by all Jito clients.
+/// Request ids are incremented across every client holding a clone.
+#[derive(Debug, Clone)]
+pub struct JsonRpcTransport {
+    client: Client,
+    next_id: Arc<AtomicU64>,
+    retry: RetryPolicy,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    auth: Option<JitoAuth>,
+    router: Option<Arc<RegionRouter>>,
+}
+
+impl JsonRpcTransport {
+    pub fn new(client: Client) -> Self {
         Self {
-            client: Client::new(),
+            client,
+            next_id: Arc::new(AtomicU64::new(1)),
+            retry: RetryPolicy::default(),
+            rate_limiter: None,
+            auth: None,
+            router: None,
         }
     }
 
-    pub async fn send_bundle(
+    /// route requests for the primary endpoint to the best region
+    pub fn with_router(mut self, router: Option<Arc<RegionRouter>>) -> Self {
+        self.router = router;
+        self
+    }
+
+    /// UUID attached to every request of this transport
+    pub fn with_auth(mut self, auth: Option<JitoAuth>) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    /// rate limiter consulted before every attempt
+    pub fn with_rate_limiter(mut self, rate_limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// retry policy applied to every call
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry
+    }
+
+    fn next_id(&self) -> u64 {
+        self.next_id.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// call a JSON-RPC method and return its `result`, retrying with the
+    /// transport's policy
+    pub async fn call<P, R>(&self, url: &str, method: &str, params: P) -> Result<R, JitoError>
+    where
+        P: Serialize,
+        R: DeserializeOwned,
+    {
+        self.call_with_retry(url, method, params, &self.retry).await
+    }
+
+    /// call a JSON-RPC method, retrying according to `retry` and the
+    /// idempotency of the method
+    pub async fn call_with_retry<P, R>(
         &self,
-        transactions: Vec<Transaction>,
-        tip_account: Option<Pubkey>,
-        tip_amount: Option<u64>,
-    ) -> Result<String, JitoError<String>> {
-        let encoded_txs: Vec<String> = transactions
-            .into_iter()
-            .map(|tx| {
-                // Serialize the entire transaction (signature + message).
-                let mut serialized = Vec::new();
-                // Number of serialized signatures
-                serialized.extend_from_slice(&(tx.signatures.len() as u64).to_le_bytes());
-                // Serialize all signatures
-                for signature in &tx.signatures {
-                    serialized.extend_from_slice(signature.as_ref());
-                }
-                // Serialization message data
-                let message_data = tx.message_data();
-                serialized.extend_from_slice(&message_data);
-                Ok(BASE64_STANDARD.encode(&serialized))
-            })
-            .collect::<Result<Vec<_>, JitoError<String>>>()?;
-        let params = BundleParams {
-            txs: encoded_txs,
-            tip_account: tip_account.map(|pk| pk.to_string()),
-            tip_amount,
-        };
-        let request = BundleRequest {
-            jsonrpc: "2.0".to_string(),
-            id: 1,
-            method: "sendBundle".to_string(),
-            params: vec![params],
+        url: &str,
+        method: &str,
+        params: P,
+        retry: &RetryPolicy,
+    ) -> Result<R, JitoError>
+    where
+        P: Serialize,
+        R: DeserializeOwned,
+    {
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0",
+            id: self.next_id(),
+            method,
+            params,
         };
-        let response = self
-            .client
-            .post(BUNDLE_RPC)
-            .json(&request)
-            .send()
+        let body = serde_json::to_vec(&request)?;
+        retry
+            .run(Idempotency::of_method(method), || {
+                self.send_request(url, method, body.clone())
+            })
             .await
-            .map_err(|e| JitoError::BundleError(format!("{:?}", e)))?;
-        self.handle_response(response).await
     }
 
-    pub async fn get_bundle_status(
-        &self,
-        bundle_id: &str,
-    ) -> Result<BundleStatus, JitoError<String>> {
-        let request = serde_json::json!({
-            "jsonrpc": "2.0",
-            "id": 1,
-            "method": "getBundleStatus",
-            "params": [bundle_id]
-        });
-        let response = self
-            .client
-            .post(BUNDLE_RPC)
-            .json(&request)
-            .send()
+    /// GET a REST document of the block engine; `path` is the rate limit key
+    pub async fn get<R: DeserializeOwned>(&self, url: &str, path: &str) -> Result<R, JitoError> {
+        self.retry
+            .run(Idempotency::Idempotent, || async {
+                let (url, region) = self.route(url);
+                self.acquire(&url, path).await?;
+                let result = fetch_json(self.authorize(self.client.get(&url))).await;
+                self.record(region, &result);
+                result
+            })
             .await
-            .map_err(|e| JitoError::BundleError(format!("{:?}", e)))?;
+    }
 
-        let status_response: BundleStatusResponse = response
-            .json()
-            .await
-            .map_err(|e| JitoError::BundleError(e.to_string()))?;
-        Ok(status_response.result)
+    fn route(&self, url: &str) -> (String, Option<Region>) {
+        match &self.router {
+            Some(router) => router.route(url),
+            None => (url.to_string(), None),
+        }
     }
 
-    async fn handle_response(
-        &self,
-        response: reqwest::Response,
-    ) -> Result<String, JitoError<String>> {
-        if !response.status().is_success() {
-            return Err(JitoError::BundleError(format!(
-                "HTTP {}: {}",
-                response.status(),
-                response
-                    .text()
-                    .await
-                    .map_err(|e| JitoError::BundleError(format!("{:?}", e)))?
-            )));
+    fn record<T>(&self, region: Option<Region>, result: &Result<T, JitoError>) {
+        if let (Some(router), Some(region)) = (&self.router, region) {
+            router.record_result(region, result);
         }
-        let bundle_response: BundleResponse = response
-            .json()
-            .await
-            .map_err(|e| JitoError::BundleError(format!("{:?}", e)))?;
+    }
+
+    fn authorize(&self, request: RequestBuilder) -> RequestBuilder {
+        let Some(auth) = &self.auth else {
+            return request;
+        };
+        let mut request = request;
+        if auth.sends_header()
+            && let Ok(mut value) = HeaderValue::from_str(auth.uuid())
+        {
+            value.set_sensitive(true);
+            request = request.header(AUTH_HEADER, value);
+        }
+        if auth.sends_query_param() {
+            request = request.query(&[("uuid", auth.uuid())]);
+        }
+        request
+    }
+
+    async fn acquire(&self, url: &str, method: &str) -> Result<(), JitoError> {
+        match &self.rate_limiter {
+            Some(rate_limiter) => rate_limiter.acquire(url, method).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn send_request<R: DeserializeOwned>(
+        &self,
+        url: &str,
+        method: &str,
+        body: Vec<u8>,
+    ) -> Result<R, JitoError> {
+        let (url, region) = self.route(url);
+        let result = self.post(&url, method, body).await;
+        self.record(region, &result);
+        result
+    }
 
-        if let Some(error) = bundle_response.error {
-            return Err(JitoError::BundleError(format!("{:?}", error)));
+    async fn post<R: DeserializeOwned>(
+        &self,
+        url: &str,
+        method: &str,
+        body: Vec<u8>,
+    ) -> Result<R, JitoError> {
+        self.acquire(url, method).await?;
+        let response = self
+            .authorize(self.client.post(url))
+            .header(CONTENT_TYPE, "application/json")
+            .body(body)
+            .send()
+            .await?;
+        let status = response.status();
+        let retry_after = retry_after(&response);
+        let body = response.text().await?;
+        // the block engine reports rpc errors with non-2xx status codes too
+        let rpc_response: JsonRpcResponse<R> = match serde_json::from_str(&body) {
+            Ok(rpc_response) => rpc_response,
+            Err(_) if !status.is_success() => {
+                return Err(JitoError::from_status(status, retry_after, body));
+            }
+            Err(e) => return Err(e.into()),
+        };
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            return Err(JitoError::RateLimited { retry_after });
         }
-        bundle_response
+        if let Some(error) = rpc_response.error {
+            return Err(JitoError::from_rpc(error, retry_after));
+        }
+        if !status.is_success() {
+            return Err(JitoError::from_status(status, retry_after, body));
+        }
+        rpc_response
             .result
-            .map(|r| r.bundle_id)
-            .ok_or_else(|| JitoError::BundleError("No result in response".to_string()))
+            .ok_or_else(|| JitoError::Serialization(format!("no result in {} response", method)))
     }
 }
 
-#[derive(Debug, Deserialize)]
-struct BundleStatusResponse {
-    result: BundleStatus,
+/// GET a JSON document, retrying with `retry`
+pub(crate) async fn get_json<R: DeserializeOwned>(
+    client: &Client,
+    url: &str,
+    retry: &RetryPolicy,
+) -> Result<R, JitoError> {
+    retry
+        .run(Idempotency::Idempotent, || fetch_json(client.get(url)))
+        .await
 }
 
-#[derive(Debug, Deserialize)]
+pub(crate) async fn fetch_json<R: DeserializeOwned>(
+    request: RequestBuilder,
+) -> Result<R, JitoError> {
+    let response = request.send().await?;
+    if !response.status().is_success() {
+        return Err(JitoError::from_response(response).await);
+    }
+    Ok(response.json().await?)
+}
+
+/// ============== bundle client ==============
+
+#[derive(Debug, Clone)]
+pub struct BundleClient {
+    transport: JsonRpcTransport,
+    url: String,
+    encoding: TxEncoding,
+}
+
+#[derive(Debug, Serialize)]
+struct EncodingParams {
+    encoding: TxEncoding,
+}
+
+/// Status reported by `getInflightBundleStatuses`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InflightStatus {
+    /// bundle id not found in the last 5 minutes
+    Invalid,
+    /// not failed, not landed, not invalid
+    Pending,
+    /// all regions marked the bundle as failed
+    Failed,
+    /// landed on-chain, see `landed_slot`
+    Landed,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct InflightBundleStatus {
+    pub bundle_id: String,
+    pub status: InflightStatus,
+    pub landed_slot: Option<u64>,
+}
+
+impl InflightBundleStatus {
+    /// slot the bundle landed in, only set when `Landed`
+    pub fn landed_slot(&self) -> Option<u64> {
+        match self.status {
+            InflightStatus::Landed => self.landed_slot,
+            _ => None,
+        }
+    }
+}
+
+/// Commitment level of a landed bundle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfirmationStatus {
+    Processed,
+    Confirmed,
+    Finalized,
+}
+
+/// Status reported by `getBundleStatuses`
+#[derive(Debug, Clone, Deserialize)]
 pub struct BundleStatus {
     pub bundle_id: String,
-    pub status: String,
-    pub slot: Option<u64>,
+    /// transaction signatures in bundle order
+    pub transactions: Vec<String>,
+    pub slot: u64,
+    pub confirmation_status: ConfirmationStatus,
+    /// `{"Ok": null}` on success, otherwise the transaction error
+    pub err: serde_json::Value,
+}
+
+impl BundleStatus {
+    pub fn is_ok(&self) -> bool {
+        self.err.get("Ok").is_some_and(|value| value.is_null())
+    }
+}
+
+impl BundleClient {
+    pub fn new(config: &JitoConfig, transport: JsonRpcTransport) -> Self {
+        Self {
+            transport,
+            url: config.url(BUNDLE_PATH),
+            encoding: config.encoding,
+        }
+    }
+
+    /// `sendBundle`: submit a bundle of legacy or versioned transactions,
+    /// returns the bundle id
+    pub async fn send_bundle<T: Into<VersionedTransaction>>(
+        &self,
+        transactions: Vec<T>,
+    ) -> Result<String, JitoError> {
+        self.send_bundle_with_retry(transactions, self.transport.retry_policy())
+            .await
+    }
+
+    /// `sendBundle` with an explicit retry policy; only connect errors are
+    /// retried since the bundle may otherwise have been accepted
+    pub async fn send_bundle_with_retry<T: Into<VersionedTransaction>>(
+        &self,
+        transactions: Vec<T>,
+        retry: &RetryPolicy,
+    ) -> Result<String, JitoError> {
+        let transactions: Vec<VersionedTransaction> =
+            transactions.into_iter().map(Into::into).collect();
+        // Canonical wire format (shortvec signatures + message)
+        let encoded_txs = encode_transactions(&transactions, self.encoding)?;
+        let params = (
+            encoded_txs,
+            EncodingParams {
+                encoding: self.encoding,
+            },
+        );
+        self.transport
+            .call_with_retry(&self.url, "sendBundle", params, retry)
+            .await
+            .map_err(|e| match e {
+                JitoError::Rpc(error) if error.code == rpc_code::INVALID_PARAMS => {
+                    JitoError::BundleRejected {
+                        reason: error.message,
+                    }
+                }
+                e => e,
+            })
+    }
+
+    /// `getBundleStatuses`: statuses of landed bundles, `None` for unknown ids.
+    /// Larger id lists are split into batches of `MAX_BUNDLE_IDS_PER_REQUEST`.
+    pub async fn get_bundle_statuses(
+        &self,
+        bundle_ids: &[String],
+    ) -> Result<Vec<Option<BundleStatus>>, JitoError> {
+        let mut statuses = Vec::with_capacity(bundle_ids.len());
+        for chunk in bundle_ids.chunks(MAX_BUNDLE_IDS_PER_REQUEST) {
+            let response: RpcContextResponse<Vec<Option<BundleStatus>>> = self
+                .transport
+                .call(&self.url, "getBundleStatuses", [chunk])
+                .await?;
+            statuses.extend(response.value);
+        }
+        Ok(statuses)
+    }
+
+    /// `getInflightBundleStatuses`: statuses of bundles submitted in the last 5 minutes.
+    /// Larger id lists are split into batches of `MAX_BUNDLE_IDS_PER_REQUEST`.
+    pub async fn get_inflight_bundle_statuses(
+        &self,
+        bundle_ids: &[String],
+    ) -> Result<Vec<InflightBundleStatus>, JitoError> {
+        let mut statuses = Vec::with_capacity(bundle_ids.len());
+        for chunk in bundle_ids.chunks(MAX_BUNDLE_IDS_PER_REQUEST) {
+            let response: RpcContextResponse<Vec<InflightBundleStatus>> = self
+                .transport
+                .call(&self.url, "getInflightBundleStatuses", [chunk])
+                .await?;
+            statuses.extend(response.value);
+        }
+        Ok(statuses)
+    }
+
+    pub async fn get_bundle_status(
+        &self,
+        bundle_id: &str,
+    ) -> Result<Option<BundleStatus>, JitoError> {
+        Ok(self
+            .get_bundle_statuses(&[bundle_id.to_string()])
+            .await?
+            .into_iter()
+            .next()
+            .flatten())
+    }
+
+    pub async fn get_inflight_bundle_status(
+        &self,
+        bundle_id: &str,
+    ) -> Result<InflightBundleStatus, JitoError> {
+        self.get_inflight_bundle_statuses(&[bundle_id.to_string()])
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| JitoError::Bundle(format!("bundle {} not found", bundle_id)))
+    }
 }
 
 /// ============== tip client ==============
-use crate::global::TIP_RPC;
 
 #[derive(Debug, Clone)]
 pub struct TipClient {
-    client: Client,
-}
-
-#[derive(Debug, Deserialize)]
-struct TipAccountsResponse {
-    tip_accounts: Vec<TipAccount>,
+    transport: JsonRpcTransport,
+    url: String,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct TipAccount {
     pub pubkey: String,
-    pub lamports_per_signature: u64,
 }
 
 impl TipClient {
-    pub fn new() -> Self {
+    pub fn new(config: &JitoConfig, transport: JsonRpcTransport) -> Self {
         Self {
-            client: Client::new(),
+            transport,
+            url: config.url(BUNDLE_PATH),
         }
     }
-    pub async fn get_tip_accounts(&self) -> Result<Vec<TipAccount>, JitoError<String>> {
-        let response = self
-            .client
-            .get(TIP_RPC)
-            .send()
-            .await
-            .map_err(|e| JitoError::TipError(format!("{:?}", e)))?;
-        if !response.status().is_success() {
-            return Err(JitoError::TipError(format!(
-                "HTTP {}: {}",
-                response.status(),
-                response
-                    .text()
-                    .await
-                    .map_err(|e| JitoError::TipError(format!("{:?}", e)))?
-            )));
-        }
-        let tip_response: TipAccountsResponse = response
-            .json()
-            .await
-            .map_err(|e| JitoError::TipError(format!("{:?}", e)))?;
-        Ok(tip_response.tip_accounts)
-    }
-    pub async fn get_optimal_tip_account(&self) -> Result<TipAccount, JitoError<String>> {
-        let tip_accounts = self.get_tip_accounts().await?;
-        tip_accounts
+    /// `getTipAccounts`
+    pub async fn get_tip_accounts(&self) -> Result<Vec<TipAccount>, JitoError> {
+        let accounts: Vec<String> = self
+            .transport
+            .call(&self.url, "getTipAccounts", [(); 0])
+            .await?;
+        Ok(accounts
             .into_iter()
-            .max_by_key(|account| account.lamports_per_signature)
-            .ok_or(JitoError::TipError("get tip accounts error".to_string()))
-    }
-    pub async fn get_recommended_tip(&self) -> Result<u64, JitoError<String>> {
-        let optimal_account = self.get_optimal_tip_account().await?;
-        Ok(optimal_account.lamports_per_signature)
+            .map(|pubkey| TipAccount { pubkey })
+            .collect())
     }
 }
 
 /// ============== Block Engine Client ==============
-use crate::global::BLOCK_EGNINE_RPC;
+use crate::global::BLOCK_EGNINE_PATH;
 
+/// REST `/api/v1/block-engine` client; the public block engine does not serve this path
 #[derive(Debug, Clone)]
 pub struct BlockEngineClient {
-    client: Client,
+    transport: JsonRpcTransport,
+    url: String,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct BlockEngineResponse {
-    pub leaders: Vec<Leader>,
     pub congestion: f64,
     pub current_slot: u64,
 }
 
-#[derive(Debug, Deserialize)]
-pub struct Leader {
-    pub pubkey: String,
-    pub slot: u64,
-}
-
 impl BlockEngineClient {
-    pub fn new() -> Self {
+    pub fn new(config: &JitoConfig, transport: JsonRpcTransport) -> Self {
         Self {
-            client: Client::new(),
+            transport,
+            url: config.url(BLOCK_EGNINE_PATH),
         }
     }
 
-    pub async fn get_block_engine_info(&self) -> Result<BlockEngineResponse, JitoError<String>> {
-        let response = self
-            .client
-            .get(BLOCK_EGNINE_RPC)
-            .send()
-            .await
-            .map_err(|e| JitoError::BlockEngineError(format!("{:?}", e)))?;
-        if !response.status().is_success() {
-            return Err(JitoError::BlockEngineError(format!(
-                "HTTP {}: {}",
-                response.status(),
-                response
-                    .text()
-                    .await
-                    .map_err(|e| JitoError::BlockEngineError(format!("{:?}", e)))?
-            )));
-        }
-        let engine_response: BlockEngineResponse = response
-            .json()
-            .await
-            .map_err(|e| JitoError::BlockEngineError(format!("{:?}", e)))?;
+    pub async fn get_block_engine_info(&self) -> Result<BlockEngineResponse, JitoError> {
+        let engine_response: BlockEngineResponse =
+            self.transport.get(&self.url, BLOCK_EGNINE_PATH).await?;
         Ok(engine_response)
     }
 
-    pub async fn get_current_leaders(&self) -> Result<Vec<Leader>, JitoError<String>> {
-        let info = self.get_block_engine_info().await?;
-        Ok(info.leaders)
-    }
-
-    pub async fn get_network_congestion(&self) -> Result<f64, JitoError<String>> {
+    pub async fn get_network_congestion(&self) -> Result<f64, JitoError> {
         let info = self.get_block_engine_info().await?;
         Ok(info.congestion)
     }
 }
 
 /// ============== Validators Client ==============
-use crate::global::VALIDATORS_RPC;
+use crate::global::VALIDATORS_PATH;
 
+/// REST `/api/v1/validators` client; the public block engine does not serve this path
 #[derive(Debug, Clone)]
 pub struct ValidatorsClient {
-    client: Client,
+    transport: JsonRpcTransport,
+    url: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -315,37 +566,20 @@ pub struct Validator {
 }
 
 impl ValidatorsClient {
-    pub fn new() -> Self {
+    pub fn new(config: &JitoConfig, transport: JsonRpcTransport) -> Self {
         Self {
-            client: Client::new(),
+            transport,
+            url: config.url(VALIDATORS_PATH),
         }
     }
 
-    pub async fn get_validators(&self) -> Result<Vec<Validator>, JitoError<String>> {
-        let response = self
-            .client
-            .get(VALIDATORS_RPC)
-            .send()
-            .await
-            .map_err(|e| JitoError::ValidatorsError(format!("{:?}", e)))?;
-        if !response.status().is_success() {
-            return Err(JitoError::ValidatorsError(format!(
-                "HTTP {}: {}",
-                response.status(),
-                response
-                    .text()
-                    .await
-                    .map_err(|e| JitoError::ValidatorsError(format!("{:?}", e)))?
-            )));
-        }
-        let validators_response: ValidatorsResponse = response
-            .json()
-            .await
-            .map_err(|e| JitoError::ValidatorsError(format!("{:?}", e)))?;
+    pub async fn get_validators(&self) -> Result<Vec<Validator>, JitoError> {
+        let validators_response: ValidatorsResponse =
+            self.transport.get(&self.url, VALIDATORS_PATH).await?;
         Ok(validators_response.validators)
     }
 
-    pub async fn get_active_validators(&self) -> Result<Vec<Validator>, JitoError<String>> {
+    pub async fn get_active_validators(&self) -> Result<Vec<Validator>, JitoError> {
         let validators = self.get_validators().await?;
         Ok(validators.into_iter().filter(|v| v.active).collect())
     }
@@ -354,7 +588,9 @@ impl ValidatorsClient {
 
 #[derive(Debug, Clone)]
 pub struct TransactionsPoolClient {
-    client: Client,
+    transport: JsonRpcTransport,
+    url: String,
+    encoding: TxEncoding,
 }
 
 #[derive(Debug, Deserialize)]
@@ -371,44 +607,46 @@ pub struct MemPoolTransaction {
 }
 
 impl TransactionsPoolClient {
-    pub fn new() -> Self {
+    pub fn new(config: &JitoConfig, transport: JsonRpcTransport) -> Self {
         Self {
-            client: Client::new(),
+            transport,
+            url: config.url(TRANSACTIONS_POOL_PATH),
+            encoding: config.encoding,
         }
     }
 
-    pub async fn get_mempool_transactions(
+    /// `sendTransaction`: forward a single transaction through the block engine,
+    /// returns the transaction signature
+    pub async fn send_transaction<T: Into<VersionedTransaction>>(
         &self,
-    ) -> Result<Vec<MemPoolTransaction>, JitoError<String>> {
-        let response = self
-            .client
-            .get(TRANSACTIONS_POOL_RPC)
-            .send()
+        transaction: T,
+    ) -> Result<String, JitoError> {
+        let transaction: VersionedTransaction = transaction.into();
+        let encoded_tx = encode_transaction(&transaction, self.encoding)?;
+        let params = (
+            encoded_tx,
+            EncodingParams {
+                encoding: self.encoding,
+            },
+        );
+        self.transport
+            .call(&self.url, "sendTransaction", params)
             .await
-            .map_err(|e| JitoError::TransactionsPoolError(format!("{:?}", e)))?;
-
-        if !response.status().is_success() {
-            return Err(JitoError::TransactionsPoolError(format!(
-                "HTTP {}: {}",
-                response.status(),
-                response
-                    .text()
-                    .await
-                    .map_err(|e| JitoError::TransactionsPoolError(format!("{:?}", e)))?
-            )));
-        }
+    }
 
-        let tx_response: TransactionsResponse = response
-            .json()
-            .await
-            .map_err(|e| JitoError::TransactionsPoolError(format!("{:?}", e)))?;
+    /// REST `/api/v1/transactions`; the public block engine does not serve it
+    pub async fn get_mempool_transactions(&self) -> Result<Vec<MemPoolTransaction>, JitoError> {
+        let tx_response: TransactionsResponse = self
+            .transport
+            .get(&self.url, TRANSACTIONS_POOL_PATH)
+            .await?;
         Ok(tx_response.transactions)
     }
 
     pub async fn get_high_priority_transactions(
         &self,
         min_priority_fee: u64,
-    ) -> Result<Vec<MemPoolTransaction>, JitoError<String>> {
+    ) -> Result<Vec<MemPoolTransaction>, JitoError> {
         let transactions = self.get_mempool_transactions().await?;
         Ok(transactions
             .into_iter()
@@ -418,11 +656,13 @@ impl TransactionsPoolClient {
 }
 
 /// ============== Health Client ==============
-use crate::global::HEALTH_RPC;
+use crate::global::HEALTH_PATH;
 
+/// REST `/api/v1/health` client; the public block engine does not serve this path
 #[derive(Debug, Clone)]
 pub struct HealthClient {
-    client: Client,
+    transport: JsonRpcTransport,
+    url: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -433,38 +673,18 @@ pub struct HealthResponse {
 }
 
 impl HealthClient {
-    pub fn new() -> Self {
+    pub fn new(config: &JitoConfig, transport: JsonRpcTransport) -> Self {
         Self {
-            client: Client::new(),
+            transport,
+            url: config.url(HEALTH_PATH),
         }
     }
 
-    pub async fn check_health(&self) -> Result<HealthResponse, JitoError<String>> {
-        let response = self
-            .client
-            .get(HEALTH_RPC)
-            .send()
-            .await
-            .map_err(|e| JitoError::HealthError(format!("{:?}", e)))?;
-
-        if !response.status().is_success() {
-            return Err(JitoError::HealthError(format!(
-                "HTTP {}: {}",
-                response.status(),
-                response
-                    .text()
-                    .await
-                    .map_err(|e| JitoError::HealthError(format!("{:?}", e)))?
-            )));
-        }
-
-        let health_response: HealthResponse = response
-            .json()
-            .await
-            .map_err(|e| JitoError::HealthError(format!("{:?}", e)))?;
+    pub async fn check_health(&self) -> Result<HealthResponse, JitoError> {
+        let health_response: HealthResponse = self.transport.get(&self.url, HEALTH_PATH).await?;
 
         if health_response.status != "healthy" {
-            return Err(JitoError::HealthError(health_response.status));
+            return Err(JitoError::Unhealthy(health_response.status));
         }
 
         Ok(health_response)
@@ -476,11 +696,13 @@ impl HealthClient {
 }
 
 /// ============== Statistics Client ==============
-use crate::global::STATISTICS_RPC;
+use crate::global::STATISTICS_PATH;
 
+/// REST `/api/v1/stats` client; the public block engine does not serve this path
 #[derive(Debug, Clone)]
 pub struct StatisticsClient {
-    client: Client,
+    transport: JsonRpcTransport,
+    url: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -493,36 +715,48 @@ pub struct StatsResponse {
 }
 
 impl StatisticsClient {
-    pub fn new() -> Self {
+    pub fn new(config: &JitoConfig, transport: JsonRpcTransport) -> Self {
         Self {
-            client: Client::new(),
+            transport,
+            url: config.url(STATISTICS_PATH),
         }
     }
-    pub async fn get_statistics(&self) -> Result<StatsResponse, JitoError<String>> {
-        let response = self
-            .client
-            .get(STATISTICS_RPC)
-            .send()
-            .await
-            .map_err(|e| JitoError::StatisticsError(format!("{:?}", e)))?;
-        if !response.status().is_success() {
-            return Err(JitoError::StatisticsError(format!(
-                "HTTP {}: {}",
-                response.status(),
-                response
-                    .text()
-                    .await
-                    .map_err(|e| JitoError::StatisticsError(format!("{:?}", e)))?
-            )));
-        }
-        let stats_response: StatsResponse = response
-            .json()
-            .await
-            .map_err(|e| JitoError::StatisticsError(format!("{:?}", e)))?;
+    pub async fn get_statistics(&self) -> Result<StatsResponse, JitoError> {
+        let stats_response: StatsResponse = self.transport.get(&self.url, STATISTICS_PATH).await?;
         Ok(stats_response)
     }
-    pub async fn get_success_rate(&self) -> Result<f64, JitoError<String>> {
+    pub async fn get_success_rate(&self) -> Result<f64, JitoError> {
         let stats = self.get_statistics().await?;
         Ok(stats.success_rate)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AuthPlacement;
+
+    #[tokio::test]
+    async fn errors_do_not_leak_the_auth_uuid() {
+        let uuid = "3f6c2a9e-secret-uuid";
+        // bind and drop a listener to get a port nothing listens on
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let url = format!("http://127.0.0.1:{}/api/v1/bundles", port);
+        for placement in [AuthPlacement::QueryParam, AuthPlacement::Both] {
+            let transport = JsonRpcTransport::new(Client::new())
+                .with_retry_policy(RetryPolicy::none())
+                .with_auth(Some(JitoAuth::new(uuid).with_placement(placement)));
+            let error = transport
+                .call::<_, String>(&url, "getTipAccounts", ())
+                .await
+                .unwrap_err();
+            assert!(matches!(error, JitoError::Transport(_)));
+            assert!(!format!("{}", error).contains(uuid));
+            assert!(!format!("{:?}", error).contains(uuid));
+        }
+    }
+}
@@ -0,0 +1,482 @@
+use std::{fmt, str::FromStr, time::Duration};
+
+use reqwest::{Client, Proxy};
+use solana_network_sdk::types::Mode;
+
+use crate::encoding::TxEncoding;
+use crate::global::{
+    AMSTERDAM_BLOCK_ENGINE_URL, FRANKFURT_BLOCK_ENGINE_URL, MAINNET_BLOCK_ENGINE_URL,
+    NY_BLOCK_ENGINE_URL, SLC_BLOCK_ENGINE_URL, TESTNET_BLOCK_ENGINE_URL, TOKYO_BLOCK_ENGINE_URL,
+};
+use crate::leader::LeaderScheduleConfig;
+use crate::rate_limit::RateLimitConfig;
+use crate::retry::RetryPolicy;
+use crate::routing::RoutingConfig;
+use crate::tip::{TipAccountPolicy, TipOracleConfig};
+use crate::types::JitoError;
+use crate::validation::BundleValidator;
+
+/// Block engine region
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Region {
+    /// geo-routed mainnet entry point
+    Mainnet,
+    Amsterdam,
+    Frankfurt,
+    NewYork,
+    Tokyo,
+    SaltLakeCity,
+    Testnet,
+}
+
+impl Region {
+    /// all mainnet regional block engines (excluding the geo-routed entry point)
+    pub const MAINNET_REGIONS: [Region; 5] = [
+        Region::Amsterdam,
+        Region::Frankfurt,
+        Region::NewYork,
+        Region::Tokyo,
+        Region::SaltLakeCity,
+    ];
+
+    /// every known region
+    pub const ALL: [Region; 7] = [
+        Region::Mainnet,
+        Region::Amsterdam,
+        Region::Frankfurt,
+        Region::NewYork,
+        Region::Tokyo,
+        Region::SaltLakeCity,
+        Region::Testnet,
+    ];
+
+    /// base url of the region's block engine
+    pub fn url(&self) -> &'static str {
+        match self {
+            Region::Mainnet => MAINNET_BLOCK_ENGINE_URL,
+            Region::Amsterdam => AMSTERDAM_BLOCK_ENGINE_URL,
+            Region::Frankfurt => FRANKFURT_BLOCK_ENGINE_URL,
+            Region::NewYork => NY_BLOCK_ENGINE_URL,
+            Region::Tokyo => TOKYO_BLOCK_ENGINE_URL,
+            Region::SaltLakeCity => SLC_BLOCK_ENGINE_URL,
+            Region::Testnet => TESTNET_BLOCK_ENGINE_URL,
+        }
+    }
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Region::Mainnet => "mainnet",
+            Region::Amsterdam => "amsterdam",
+            Region::Frankfurt => "frankfurt",
+            Region::NewYork => "ny",
+            Region::Tokyo => "tokyo",
+            Region::SaltLakeCity => "slc",
+            Region::Testnet => "testnet",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Region {
+    type Err = JitoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "mainnet" => Ok(Region::Mainnet),
+            "amsterdam" | "ams" => Ok(Region::Amsterdam),
+            "frankfurt" | "fra" => Ok(Region::Frankfurt),
+            "ny" | "newyork" | "new-york" => Ok(Region::NewYork),
+            "tokyo" => Ok(Region::Tokyo),
+            "slc" | "saltlakecity" | "salt-lake-city" => Ok(Region::SaltLakeCity),
+            "testnet" => Ok(Region::Testnet),
+            _ => Err(JitoError::Config(format!(
+                "unknown block engine region: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// Where the block engine lives: a known region or an arbitrary base url
+/// (e.g. a local stand-in server).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Endpoint {
+    Region(Region),
+    Custom(String),
+}
+
+impl Endpoint {
+    /// base url without trailing slash
+    pub fn base_url(&self) -> &str {
+        match self {
+            Endpoint::Region(region) => region.url(),
+            Endpoint::Custom(url) => url.trim_end_matches('/'),
+        }
+    }
+}
+
+/// Where the auth UUID is sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AuthPlacement {
+    /// `x-jito-auth` header
+    #[default]
+    Header,
+    /// `?uuid=` query parameter
+    QueryParam,
+    Both,
+}
+
+/// Approved UUID for higher block engine rate limits
+#[derive(Clone, PartialEq, Eq)]
+pub struct JitoAuth {
+    uuid: String,
+    pub placement: AuthPlacement,
+}
+
+impl JitoAuth {
+    pub fn new(uuid: impl Into<String>) -> Self {
+        Self {
+            uuid: uuid.into(),
+            placement: AuthPlacement::default(),
+        }
+    }
+
+    pub fn with_placement(mut self, placement: AuthPlacement) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    pub fn uuid(&self) -> &str {
+        &self.uuid
+    }
+
+    pub fn sends_header(&self) -> bool {
+        matches!(self.placement, AuthPlacement::Header | AuthPlacement::Both)
+    }
+
+    pub fn sends_query_param(&self) -> bool {
+        matches!(
+            self.placement,
+            AuthPlacement::QueryParam | AuthPlacement::Both
+        )
+    }
+}
+
+impl fmt::Debug for JitoAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JitoAuth")
+            .field("uuid", &"<redacted>")
+            .field("placement", &self.placement)
+            .finish()
+    }
+}
+
+/// HTTP protocol preference
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HttpVersion {
+    /// negotiated through TLS ALPN, HTTP/2 when the server offers it
+    #[default]
+    Auto,
+    Http1Only,
+    /// HTTP/2 without negotiation
+    Http2PriorKnowledge,
+}
+
+/// Settings of the HTTP client shared by all sub-clients
+#[derive(Debug, Clone)]
+pub struct HttpConfig {
+    pub connect_timeout: Option<Duration>,
+    /// whole request timeout, including reading the body
+    pub request_timeout: Option<Duration>,
+    pub pool_idle_timeout: Option<Duration>,
+    pub pool_max_idle_per_host: usize,
+    pub tcp_keepalive: Option<Duration>,
+    pub tcp_nodelay: bool,
+    pub http_version: HttpVersion,
+    /// proxy url for all requests, e.g. `http://127.0.0.1:8080`
+    pub proxy: Option<String>,
+    pub user_agent: String,
+    /// preconfigured client used instead of building one from these settings
+    pub client: Option<Client>,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout: Some(Duration::from_secs(5)),
+            request_timeout: Some(Duration::from_secs(15)),
+            pool_idle_timeout: Some(Duration::from_secs(90)),
+            pool_max_idle_per_host: 8,
+            tcp_keepalive: Some(Duration::from_secs(30)),
+            tcp_nodelay: true,
+            http_version: HttpVersion::default(),
+            proxy: None,
+            user_agent: concat!("jito-sdk/", env!("CARGO_PKG_VERSION")).to_string(),
+            client: None,
+        }
+    }
+}
+
+impl HttpConfig {
+    pub fn with_connect_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    pub fn with_request_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    pub fn with_keep_alive(
+        mut self,
+        pool_idle_timeout: Option<Duration>,
+        tcp_keepalive: Option<Duration>,
+    ) -> Self {
+        self.pool_idle_timeout = pool_idle_timeout;
+        self.tcp_keepalive = tcp_keepalive;
+        self
+    }
+
+    pub fn with_pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = max;
+        self
+    }
+
+    pub fn with_tcp_nodelay(mut self, enabled: bool) -> Self {
+        self.tcp_nodelay = enabled;
+        self
+    }
+
+    pub fn with_http_version(mut self, http_version: HttpVersion) -> Self {
+        self.http_version = http_version;
+        self
+    }
+
+    pub fn with_proxy(mut self, proxy: impl Into<String>) -> Self {
+        self.proxy = Some(proxy.into());
+        self
+    }
+
+    pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// use a preconfigured client; the other settings are ignored
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// the preconfigured client, or a new one built from the settings
+    pub fn build_client(&self) -> Result<Client, JitoError> {
+        if let Some(client) = &self.client {
+            return Ok(client.clone());
+        }
+        let mut builder = Client::builder()
+            .pool_idle_timeout(self.pool_idle_timeout)
+            .pool_max_idle_per_host(self.pool_max_idle_per_host)
+            .tcp_keepalive(self.tcp_keepalive)
+            .tcp_nodelay(self.tcp_nodelay)
+            .user_agent(&self.user_agent);
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.request_timeout {
+            builder = builder.timeout(timeout);
+        }
+        builder = match self.http_version {
+            HttpVersion::Auto => builder,
+            HttpVersion::Http1Only => builder.http1_only(),
+            HttpVersion::Http2PriorKnowledge => builder.http2_prior_knowledge(),
+        };
+        if let Some(proxy) = &self.proxy {
+            let proxy = Proxy::all(proxy)
+                .map_err(|e| JitoError::Config(format!("invalid proxy {}: {}", proxy, e)))?;
+            builder = builder.proxy(proxy);
+        }
+        builder
+            .build()
+            .map_err(|e| JitoError::Config(format!("failed to build http client: {}", e)))
+    }
+}
+
+/// Jito client configuration
+#[derive(Debug, Clone)]
+pub struct JitoConfig {
+    pub endpoint: Endpoint,
+    pub solana_mode: Mode,
+    pub encoding: TxEncoding,
+    pub tip_oracle: TipOracleConfig,
+    pub tip_account_policy: TipAccountPolicy,
+    /// Jito-enabled solana rpc serving `simulateBundle`
+    pub simulation_rpc_url: Option<String>,
+    /// retry policy of every block engine request
+    pub retry: RetryPolicy,
+    /// client-side rate limits shared by all sub-clients
+    pub rate_limit: RateLimitConfig,
+    /// UUID attached to every block engine request
+    pub auth: Option<JitoAuth>,
+    pub http: HttpConfig,
+    /// regions a bundle is sent to by `Jito::send_bundle_fanout`
+    pub fanout_regions: Vec<Region>,
+    /// latency-aware region routing, disabled when `None`
+    pub routing: Option<RoutingConfig>,
+    /// source of the Jito validator set used for leader-aware submission
+    pub leader_schedule: LeaderScheduleConfig,
+    /// rules checked by `Jito::validate_bundle`; the tip accounts are
+    /// replaced by the current Jito tip accounts
+    pub validation: BundleValidator,
+}
+
+impl Default for JitoConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: Endpoint::Region(Region::Mainnet),
+            solana_mode: Mode::MAIN,
+            encoding: TxEncoding::default(),
+            tip_oracle: TipOracleConfig::default(),
+            tip_account_policy: TipAccountPolicy::default(),
+            simulation_rpc_url: None,
+            retry: RetryPolicy::default(),
+            rate_limit: RateLimitConfig::default(),
+            auth: None,
+            http: HttpConfig::default(),
+            fanout_regions: Region::MAINNET_REGIONS.to_vec(),
+            routing: None,
+            leader_schedule: LeaderScheduleConfig::default(),
+            validation: BundleValidator::default(),
+        }
+    }
+}
+
+impl JitoConfig {
+    /// create a mainnet config
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// create a testnet config (testnet block engine + testnet solana rpc)
+    pub fn testnet() -> Self {
+        Self {
+            endpoint: Endpoint::Region(Region::Testnet),
+            solana_mode: Mode::TEST,
+            fanout_regions: vec![Region::Testnet],
+            ..Self::default()
+        }
+    }
+
+    /// route requests to a specific block engine region
+    pub fn with_region(mut self, region: Region) -> Self {
+        self.endpoint = Endpoint::Region(region);
+        self
+    }
+
+    /// route requests to an arbitrary base url, e.g. `http://127.0.0.1:8080`
+    pub fn with_base_url(mut self, url: impl Into<String>) -> Self {
+        self.endpoint = Endpoint::Custom(url.into());
+        self
+    }
+
+    /// solana rpc network used for blockhashes and account queries
+    pub fn with_solana_mode(mut self, mode: Mode) -> Self {
+        self.solana_mode = mode;
+        self
+    }
+
+    /// transaction encoding used when submitting to the block engine
+    pub fn with_encoding(mut self, encoding: TxEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// tip floor feed and static fallback used for tip recommendations
+    pub fn with_tip_oracle(mut self, tip_oracle: TipOracleConfig) -> Self {
+        self.tip_oracle = tip_oracle;
+        self
+    }
+
+    /// how tip accounts are picked for bundles
+    pub fn with_tip_account_policy(mut self, policy: TipAccountPolicy) -> Self {
+        self.tip_account_policy = policy;
+        self
+    }
+
+    /// Jito-enabled solana rpc used for `simulateBundle`
+    pub fn with_simulation_rpc_url(mut self, url: impl Into<String>) -> Self {
+        self.simulation_rpc_url = Some(url.into());
+        self
+    }
+
+    /// backoff and retry limit of block engine requests
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// client-side rate limits per method and region
+    pub fn with_rate_limit(mut self, rate_limit: RateLimitConfig) -> Self {
+        self.rate_limit = rate_limit;
+        self
+    }
+
+    /// authenticate block engine requests, e.g.
+    /// `with_auth(JitoAuth::new(uuid).with_placement(AuthPlacement::Both))`
+    pub fn with_auth(mut self, auth: JitoAuth) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// authenticate block engine requests with the `x-jito-auth` header
+    pub fn with_uuid(self, uuid: impl Into<String>) -> Self {
+        self.with_auth(JitoAuth::new(uuid))
+    }
+
+    /// timeouts, pooling and proxy of the shared HTTP client
+    pub fn with_http(mut self, http: HttpConfig) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// use a preconfigured `reqwest::Client` for all requests
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.http.client = Some(client);
+        self
+    }
+
+    /// regions used for fan-out submission
+    pub fn with_fanout_regions(mut self, regions: impl Into<Vec<Region>>) -> Self {
+        self.fanout_regions = regions.into();
+        self
+    }
+
+    /// route requests to the fastest healthy region, see
+    /// `Jito::spawn_region_probing`
+    pub fn with_routing(mut self, routing: RoutingConfig) -> Self {
+        self.routing = Some(routing);
+        self
+    }
+
+    pub fn with_leader_schedule(mut self, leader_schedule: LeaderScheduleConfig) -> Self {
+        self.leader_schedule = leader_schedule;
+        self
+    }
+
+    pub fn with_validation(mut self, validation: BundleValidator) -> Self {
+        self.validation = validation;
+        self
+    }
+
+    /// block engine base url
+    pub fn block_engine_url(&self) -> &str {
+        self.endpoint.base_url()
+    }
+
+    /// full url of a block engine api path
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.block_engine_url(), path)
+    }
+}
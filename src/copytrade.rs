@@ -2,11 +2,14 @@ use crate::BackrunConfig;
 use crate::client::MemPoolTransaction;
 
 use crate::Jito;
+use crate::tip::{TipPlacement, TipStrategy};
 use crate::types::JitoError;
 use solana_sdk::{
-    message::Message, pubkey::Pubkey, signature::Keypair, signer::Signer, transaction::Transaction,
+    message::Message,
+    signer::Signer,
+    transaction::{Transaction, VersionedTransaction},
 };
-use std::{str::FromStr, sync::Arc};
+use std::sync::Arc;
 use tokio::time::{Duration, sleep};
 pub struct CopyTrade {
     jito: Arc<Jito>,
@@ -20,32 +23,36 @@ impl CopyTrade {
     }
     pub async fn exe_backrun(
         &self,
-        wallet: &Keypair,
-        target_transaction: Transaction,
-        backrun_tx: Transaction,
+        wallet: &dyn Signer,
+        target_transaction: impl Into<VersionedTransaction>,
+        backrun_tx: impl Into<VersionedTransaction>,
         config: &BackrunConfig,
-    ) -> Result<String, JitoError<String>> {
-        let tip_account = self.jito.get_optimal_tip_account().await?;
-        let tip_pubkey = Pubkey::from_str(&tip_account.pubkey)
-            .map_err(|e| JitoError::SerializationError(e.to_string()))?;
-        let tip_amount = config.min_priority_fee;
-        let bundle_id = self
+    ) -> Result<String, JitoError> {
+        let tip = TipStrategy::new(config.min_priority_fee)
+            .with_placement(TipPlacement::SeparateTransaction);
+        let tip_account = self.jito.resolve_tip_account(&tip).await?;
+        let backrun_tx: VersionedTransaction = backrun_tx.into();
+        // the tip rides on the backrun's blockhash so it only pays if the bundle lands
+        let tip_tx =
+            tip.build_transaction(wallet, &tip_account, *backrun_tx.message.recent_blockhash())?;
+        let transactions = vec![target_transaction.into(), backrun_tx, tip_tx.into()];
+        // the target transaction carries its own blockhash
+        let validator = self
             .jito
-            .bundle
-            .send_bundle(
-                vec![target_transaction, backrun_tx],
-                Some(tip_pubkey),
-                Some(tip_amount),
-            )
+            .bundle_validator()
             .await
-            .map_err(|e| JitoError::BundleError(e.to_string()))?;
+            .with_require_single_blockhash(false);
+        self.jito
+            .validate_bundle_with(&validator, &transactions)
+            .await?;
+        let bundle_id = self.jito.bundle.send_bundle(transactions).await?;
         log::info!("Backrun bundle sent: {}", bundle_id);
         Ok(bundle_id)
     }
 
     pub async fn monitor_for_backrun_opportunities(
         &self,
-        wallet: Arc<Keypair>,
+        wallet: Arc<dyn Signer + Send + Sync>,
         config: BackrunConfig,
     ) {
         log::info!("Starting backrun monitoring...");
@@ -60,13 +67,11 @@ impl CopyTrade {
                     if let Ok(backrun_tx) = self
                         .build_backrun_transaction(&wallet, target_tx, config.profit_threshold)
                         .await
-                    {
-                        if let Err(e) = self
+                        && let Err(e) = self
                             .exe_backrun(&wallet, Transaction::default(), backrun_tx, &config)
                             .await
-                        {
-                            log::error!("Backrun execution failed: {}", e);
-                        }
+                    {
+                        log::error!("Backrun execution failed: {}", e);
                     }
                 }
             }
@@ -74,22 +79,21 @@ impl CopyTrade {
         }
     }
 
+    #[allow(unreachable_code, unused_variables)]
     async fn build_backrun_transaction(
         &self,
-        wallet: &Keypair,
+        wallet: &dyn Signer,
         target_tx: &MemPoolTransaction,
         profit_threshold: u64,
-    ) -> Result<Transaction, JitoError<String>> {
+    ) -> Result<Transaction, JitoError> {
         // Build copy trading logic, analyze target trades, and construct corresponding copy trading strategies.
         todo!();
-        let recent_blockhash = self
-            .jito
-            .solana
-            .client_arc()
-            .get_latest_blockhash()
-            .await
-            .map_err(|e| JitoError::Error(format!("{:?}", e)))?;
+        let recent_blockhash = self.jito.solana.client_arc().get_latest_blockhash().await?;
         let message = Message::new_with_blockhash(&[], Some(&wallet.pubkey()), &recent_blockhash);
-        Ok(Transaction::new(&[wallet], message, recent_blockhash))
+        let mut transaction = Transaction::new_unsigned(message);
+        transaction
+            .try_sign(&[wallet], recent_blockhash)
+            .map_err(|e| JitoError::Transaction(format!("{:?}", e)))?;
+        Ok(transaction)
     }
 }
@@ -0,0 +1,175 @@
+use base64::{Engine, prelude::BASE64_STANDARD};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::types::JitoError;
+
+/// Encoding of serialized transactions sent to the block engine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TxEncoding {
+    Base58,
+    #[default]
+    Base64,
+}
+
+/// Serialize a transaction (legacy `Transaction` or `VersionedTransaction`)
+/// into canonical Solana wire bytes: shortvec signature count, signatures,
+/// then the serialized message.
+pub fn serialize_transaction<T: Serialize>(tx: &T) -> Result<Vec<u8>, JitoError> {
+    bincode::serde::encode_to_vec(tx, bincode::config::legacy())
+        .map_err(|e| JitoError::Serialization(e.to_string()))
+}
+
+/// Deserialize canonical Solana wire bytes back into a transaction.
+pub fn deserialize_transaction<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, JitoError> {
+    let (tx, read) = bincode::serde::decode_from_slice(bytes, bincode::config::legacy())
+        .map_err(|e| JitoError::Serialization(e.to_string()))?;
+    if read != bytes.len() {
+        return Err(JitoError::Serialization(format!(
+            "trailing bytes after transaction: {} of {} consumed",
+            read,
+            bytes.len()
+        )));
+    }
+    Ok(tx)
+}
+
+/// Serialize and encode a transaction as a base58/base64 string.
+pub fn encode_transaction<T: Serialize>(tx: &T, encoding: TxEncoding) -> Result<String, JitoError> {
+    let bytes = serialize_transaction(tx)?;
+    Ok(match encoding {
+        TxEncoding::Base58 => bs58::encode(bytes).into_string(),
+        TxEncoding::Base64 => BASE64_STANDARD.encode(bytes),
+    })
+}
+
+/// Decode a base58/base64 string into a transaction.
+pub fn decode_transaction<T: DeserializeOwned>(
+    encoded: &str,
+    encoding: TxEncoding,
+) -> Result<T, JitoError> {
+    let bytes = match encoding {
+        TxEncoding::Base58 => bs58::decode(encoded)
+            .into_vec()
+            .map_err(|e| JitoError::Serialization(e.to_string()))?,
+        TxEncoding::Base64 => BASE64_STANDARD
+            .decode(encoded)
+            .map_err(|e| JitoError::Serialization(e.to_string()))?,
+    };
+    deserialize_transaction(&bytes)
+}
+
+/// Encode a list of transactions with the same encoding.
+pub fn encode_transactions<T: Serialize>(
+    txs: &[T],
+    encoding: TxEncoding,
+) -> Result<Vec<String>, JitoError> {
+    txs.iter()
+        .map(|tx| encode_transaction(tx, encoding))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::hash::Hash;
+    use solana_sdk::instruction::AccountMeta;
+    use solana_sdk::message::{
+        AddressLookupTableAccount, Instruction, Message, VersionedMessage, v0,
+    };
+    use solana_sdk::pubkey::Pubkey;
+    use solana_sdk::signature::Keypair;
+    use solana_sdk::signer::Signer;
+    use solana_sdk::transaction::{Transaction, VersionedTransaction};
+
+    use super::*;
+
+    const ENCODINGS: [TxEncoding; 2] = [TxEncoding::Base58, TxEncoding::Base64];
+
+    fn instruction(payer: &Pubkey, recipient: &Pubkey) -> Instruction {
+        Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[2, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0],
+            vec![
+                AccountMeta::new(*payer, true),
+                AccountMeta::new(*recipient, false),
+            ],
+        )
+    }
+
+    fn legacy_transaction() -> Transaction {
+        let payer = Keypair::new();
+        let instruction = instruction(&payer.pubkey(), &Pubkey::new_unique());
+        let message = Message::new(&[instruction], Some(&payer.pubkey()));
+        Transaction::new(&[&payer], message, Hash::new_unique())
+    }
+
+    fn v0_transaction() -> VersionedTransaction {
+        let payer = Keypair::new();
+        let recipient = Pubkey::new_unique();
+        let lookup_table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: vec![Pubkey::new_unique(), recipient],
+        };
+        let instruction = instruction(&payer.pubkey(), &recipient);
+        let message = v0::Message::try_compile(
+            &payer.pubkey(),
+            &[instruction],
+            &[lookup_table],
+            Hash::new_unique(),
+        )
+        .unwrap();
+        assert_eq!(message.address_table_lookups.len(), 1);
+        VersionedTransaction::try_new(VersionedMessage::V0(message), &[&payer]).unwrap()
+    }
+
+    #[test]
+    fn legacy_round_trip() {
+        let tx = legacy_transaction();
+        for encoding in ENCODINGS {
+            let encoded = encode_transaction(&tx, encoding).unwrap();
+            let decoded: Transaction = decode_transaction(&encoded, encoding).unwrap();
+            assert_eq!(decoded, tx);
+        }
+    }
+
+    #[test]
+    fn v0_round_trip() {
+        let tx = v0_transaction();
+        for encoding in ENCODINGS {
+            let encoded = encode_transaction(&tx, encoding).unwrap();
+            let decoded: VersionedTransaction = decode_transaction(&encoded, encoding).unwrap();
+            assert_eq!(decoded, tx);
+        }
+    }
+
+    #[test]
+    fn serialize_matches_bincode1() {
+        let legacy = legacy_transaction();
+        assert_eq!(
+            serialize_transaction(&legacy).unwrap(),
+            bincode1::serialize(&legacy).unwrap()
+        );
+        let versioned = v0_transaction();
+        assert_eq!(
+            serialize_transaction(&versioned).unwrap(),
+            bincode1::serialize(&versioned).unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_trailing_bytes() {
+        let mut bytes = serialize_transaction(&v0_transaction()).unwrap();
+        bytes.push(0);
+        assert!(matches!(
+            deserialize_transaction::<VersionedTransaction>(&bytes),
+            Err(JitoError::Serialization(_))
+        ));
+        for encoding in ENCODINGS {
+            let encoded = match encoding {
+                TxEncoding::Base58 => bs58::encode(&bytes).into_string(),
+                TxEncoding::Base64 => BASE64_STANDARD.encode(&bytes),
+            };
+            assert!(decode_transaction::<VersionedTransaction>(&encoded, encoding).is_err());
+        }
+    }
+}
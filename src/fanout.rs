@@ -0,0 +1,176 @@
+use std::time::{Duration, Instant};
+
+use futures::future::join_all;
+use solana_sdk::transaction::VersionedTransaction;
+
+use crate::Jito;
+use crate::client::{BundleClient, JsonRpcTransport};
+use crate::config::{JitoConfig, Region};
+use crate::retry::RetryPolicy;
+use crate::types::JitoError;
+use crate::watch::{BundleOutcome, WatchOptions};
+
+/// Result of submitting a bundle to one region
+#[derive(Debug)]
+pub struct RegionSubmission {
+    pub region: Region,
+    pub result: Result<String, JitoError>,
+    /// round trip of the `sendBundle` call, including client-side throttling
+    pub latency: Duration,
+}
+
+/// Result of a fan-out submission, submissions ordered by latency
+#[derive(Debug)]
+pub struct FanoutResult {
+    /// bundle id of the first region that accepted the bundle
+    pub bundle_id: Option<String>,
+    pub accepted_region: Option<Region>,
+    pub submissions: Vec<RegionSubmission>,
+}
+
+impl FanoutResult {
+    pub fn is_accepted(&self) -> bool {
+        self.bundle_id.is_some()
+    }
+
+    /// distinct bundle ids returned by the regions. The id is derived from
+    /// the transaction signatures, so a bundle accepted everywhere has a
+    /// single id and only needs to be polled once, see
+    /// `Jito::wait_for_fanout`.
+    pub fn bundle_ids(&self) -> Vec<String> {
+        let mut bundle_ids: Vec<String> = Vec::new();
+        for submission in &self.submissions {
+            if let Ok(bundle_id) = &submission.result
+                && !bundle_ids.contains(bundle_id)
+            {
+                bundle_ids.push(bundle_id.clone());
+            }
+        }
+        bundle_ids
+    }
+
+    /// error of the fastest region when no region accepted the bundle
+    pub fn first_error(&self) -> Option<&JitoError> {
+        self.submissions
+            .iter()
+            .find_map(|submission| submission.result.as_ref().err())
+    }
+}
+
+/// Submits the same bundle to several block engine regions concurrently.
+/// Each region goes through the shared transport without region routing,
+/// so every leg reaches its own region and is throttled by its own rate
+/// limit bucket.
+#[derive(Debug, Clone)]
+pub struct FanoutClient {
+    regions: Vec<(Region, BundleClient)>,
+    retry: RetryPolicy,
+}
+
+impl FanoutClient {
+    pub fn new(config: &JitoConfig, transport: JsonRpcTransport) -> Self {
+        let retry = transport.retry_policy().clone();
+        let regions = config
+            .fanout_regions
+            .iter()
+            .map(|region| {
+                let region_config = config.clone().with_region(*region);
+                (
+                    *region,
+                    BundleClient::new(&region_config, transport.clone()),
+                )
+            })
+            .collect();
+        Self { regions, retry }
+    }
+
+    pub fn regions(&self) -> Vec<Region> {
+        self.regions.iter().map(|(region, _)| *region).collect()
+    }
+
+    /// `sendBundle` to every configured region at once
+    pub async fn send_bundle(
+        &self,
+        transactions: Vec<VersionedTransaction>,
+    ) -> Result<FanoutResult, JitoError> {
+        self.send_bundle_with_retry(transactions, &self.retry).await
+    }
+
+    /// `send_bundle` with an explicit retry policy for each region
+    pub async fn send_bundle_with_retry(
+        &self,
+        transactions: Vec<VersionedTransaction>,
+        retry: &RetryPolicy,
+    ) -> Result<FanoutResult, JitoError> {
+        if self.regions.is_empty() {
+            return Err(JitoError::Config(
+                "no fan-out regions configured".to_string(),
+            ));
+        }
+        let mut submissions = join_all(self.regions.iter().map(|(region, client)| {
+            let transactions = transactions.clone();
+            async move {
+                let started = Instant::now();
+                let result = client.send_bundle_with_retry(transactions, retry).await;
+                RegionSubmission {
+                    region: *region,
+                    result,
+                    latency: started.elapsed(),
+                }
+            }
+        }))
+        .await;
+        submissions.sort_by_key(|submission| submission.latency);
+        let accepted = submissions.iter().find_map(|submission| {
+            let bundle_id = submission.result.as_ref().ok()?;
+            Some((submission.region, bundle_id.clone()))
+        });
+        for submission in &submissions {
+            match &submission.result {
+                Ok(_) => log::debug!(
+                    "Bundle accepted by {} in {:?}",
+                    submission.region,
+                    submission.latency
+                ),
+                Err(e) => log::warn!("Bundle rejected by {}: {}", submission.region, e),
+            }
+        }
+        Ok(FanoutResult {
+            accepted_region: accepted.as_ref().map(|(region, _)| *region),
+            bundle_id: accepted.map(|(_, bundle_id)| bundle_id),
+            submissions,
+        })
+    }
+}
+
+impl Jito {
+    /// submit a bundle to all `JitoConfig::fanout_regions` concurrently,
+    /// after `Jito::validate_bundle`
+    pub async fn send_bundle_fanout<T: Into<VersionedTransaction>>(
+        &self,
+        transactions: Vec<T>,
+    ) -> Result<FanoutResult, JitoError> {
+        let transactions: Vec<VersionedTransaction> =
+            transactions.into_iter().map(Into::into).collect();
+        self.validate_bundle(&transactions).await?;
+        self.fanout.send_bundle(transactions).await
+    }
+
+    /// Watch every distinct bundle id of a fan-out submission, see
+    /// `FanoutResult::bundle_ids`. Regions that returned the same id are
+    /// polled once; empty when no region accepted the bundle.
+    pub async fn wait_for_fanout(
+        &self,
+        result: &FanoutResult,
+        options: WatchOptions,
+    ) -> Vec<(String, BundleOutcome)> {
+        join_all(result.bundle_ids().into_iter().map(|bundle_id| {
+            let options = options.clone();
+            async move {
+                let outcome = self.wait_for_bundle(&bundle_id, options).await;
+                (bundle_id, outcome)
+            }
+        }))
+        .await
+    }
+}
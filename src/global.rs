@@ -1,14 +1,56 @@
-/// Core Bundle Endpoints RPC
-pub const BUNDLE_RPC: &str = "https://mainnet.block-engine.jito.wtf/api/v1/bundles";
-/// Tip Endpoints RPC
-pub const TIP_RPC: &str = "https://mainnet.block-engine.jito.wtf/api/v1/tip-accounts";
-/// Block Egnine Endpoints RPC
-pub const BLOCK_EGNINE_RPC: &str = "https://mainnet.block-engine.jito.wtf/api/v1/block-engine";
-/// Verify Node Information RPC
-pub const VALIDATORS_RPC: &str = "https://mainnet.block-engine.jito.wtf/api/v1/validators";
-/// Trading Pool RPC
-pub const TRANSACTIONS_POOL_RPC: &str = "https://mainnet.block-engine.jito.wtf/api/v1/transactions";
-/// Health RPC
-pub const HEALTH_RPC: &str = "https://mainnet.block-engine.jito.wtf/api/v1/health";
-/// Statistics RPC
-pub const STATISTICS_RPC: &str = "https://mainnet.block-engine.jito.wtf/api/v1/stats";
+/// Mainnet Block Engine URL (geo-routed)
+pub const MAINNET_BLOCK_ENGINE_URL: &str = "https://mainnet.block-engine.jito.wtf";
+/// Amsterdam Block Engine URL
+pub const AMSTERDAM_BLOCK_ENGINE_URL: &str = "https://amsterdam.mainnet.block-engine.jito.wtf";
+/// Frankfurt Block Engine URL
+pub const FRANKFURT_BLOCK_ENGINE_URL: &str = "https://frankfurt.mainnet.block-engine.jito.wtf";
+/// New York Block Engine URL
+pub const NY_BLOCK_ENGINE_URL: &str = "https://ny.mainnet.block-engine.jito.wtf";
+/// Tokyo Block Engine URL
+pub const TOKYO_BLOCK_ENGINE_URL: &str = "https://tokyo.mainnet.block-engine.jito.wtf";
+/// Salt Lake City Block Engine URL
+pub const SLC_BLOCK_ENGINE_URL: &str = "https://slc.mainnet.block-engine.jito.wtf";
+/// Bundle landed tip percentiles feed
+pub const TIP_FLOOR_URL: &str = "https://bundles.jito.wtf/api/v1/bundles/tip_floor";
+/// Validators with their Jito client status, keyed by vote account
+pub const JITO_VALIDATORS_URL: &str = "https://kobe.mainnet.jito.network/api/v1/validators";
+/// Testnet Block Engine URL
+pub const TESTNET_BLOCK_ENGINE_URL: &str = "https://testnet.block-engine.jito.wtf";
+
+/// Known mainnet Jito tip accounts, used when getTipAccounts is unavailable
+pub const MAINNET_TIP_ACCOUNTS: [&str; 8] = [
+    "96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5",
+    "HFqU5x63VTqvQss8hp11i4wVV8bD44PvwucfZ2bU7gRe",
+    "Cw8CFyM9FkoMi7K7Crf6HNQqf4uEMzpKw6QNghXLvLkY",
+    "ADaUMid9yfUytqMBgopwjb2DTLSokTSzL1zt6iGPaS49",
+    "DfXygSm4jCyNCybVYYK6DwvWqjKee8pbDmJGcLWNDXjh",
+    "ADuUkR4vqLUMWXxW9gh6D6L8pMSawimctcNZ5pGwDcEt",
+    "DttWaMuVvTiduZRnguLF7jNxTgiMBZ1hyAumKUiL2KRL",
+    "3AVi9Tg9Uo68tJfuvoKvqKNWKkC5wPdSSdeBnizKZ6jT",
+];
+
+/// Max bundle ids per getBundleStatuses / getInflightBundleStatuses call
+pub const MAX_BUNDLE_IDS_PER_REQUEST: usize = 5;
+
+/// Max transactions in a bundle
+pub const MAX_BUNDLE_TRANSACTIONS: usize = 5;
+/// Max serialized transaction size (packet data size)
+pub const MAX_TRANSACTION_SIZE: usize = 1232;
+/// Max compute units a transaction may request
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// Core Bundle Endpoints path
+pub const BUNDLE_PATH: &str = "/api/v1/bundles";
+/// Block Egnine Endpoints path
+pub const BLOCK_EGNINE_PATH: &str = "/api/v1/block-engine";
+/// Verify Node Information path
+pub const VALIDATORS_PATH: &str = "/api/v1/validators";
+/// Trading Pool path
+pub const TRANSACTIONS_POOL_PATH: &str = "/api/v1/transactions";
+/// Health path
+pub const HEALTH_PATH: &str = "/api/v1/health";
+/// Statistics path
+pub const STATISTICS_PATH: &str = "/api/v1/stats";
+
+/// Header carrying the approved auth UUID
+pub const AUTH_HEADER: &str = "x-jito-auth";
@@ -0,0 +1,192 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use reqwest::Client;
+use serde::Deserialize;
+use solana_sdk::pubkey::Pubkey;
+use tokio::sync::RwLock;
+use tokio::time::sleep;
+
+use crate::Jito;
+use crate::client::get_json;
+use crate::global::JITO_VALIDATORS_URL;
+use crate::retry::RetryPolicy;
+use crate::types::JitoError;
+
+/// approximate slot duration
+pub const SLOT_DURATION: Duration = Duration::from_millis(400);
+
+#[derive(Debug, Clone)]
+pub struct LeaderScheduleConfig {
+    /// list of validators with their Jito status
+    pub validators_url: String,
+    /// how long the set of Jito validators is reused
+    pub validators_ttl: Duration,
+}
+
+impl Default for LeaderScheduleConfig {
+    fn default() -> Self {
+        Self {
+            validators_url: JITO_VALIDATORS_URL.to_string(),
+            validators_ttl: Duration::from_secs(600),
+        }
+    }
+}
+
+/// Leader of an upcoming slot
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Leader {
+    /// validator identity
+    pub pubkey: Pubkey,
+    pub slot: u64,
+    /// the validator runs the Jito client and can include bundles
+    pub is_jito: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ValidatorsResponse {
+    validators: Vec<ValidatorInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ValidatorInfo {
+    vote_account: String,
+    #[serde(default)]
+    running_jito: bool,
+}
+
+/// Tracks which upcoming leaders run the Jito client. Leaders come from the
+/// solana leader schedule; the Jito validator list is keyed by vote account
+/// and mapped to identities through `getVoteAccounts`.
+#[derive(Debug)]
+pub struct LeaderTracker {
+    client: Client,
+    config: LeaderScheduleConfig,
+    retry: RetryPolicy,
+    jito_identities: RwLock<Option<(Instant, Arc<HashSet<Pubkey>>)>>,
+}
+
+impl LeaderTracker {
+    pub fn new(config: LeaderScheduleConfig) -> Self {
+        Self {
+            client: Client::new(),
+            config,
+            retry: RetryPolicy::default(),
+            jito_identities: RwLock::new(None),
+        }
+    }
+
+    /// use a shared HTTP client
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// vote accounts of validators running the Jito client
+    pub async fn fetch_jito_vote_accounts(&self) -> Result<HashSet<String>, JitoError> {
+        let response: ValidatorsResponse =
+            get_json(&self.client, &self.config.validators_url, &self.retry).await?;
+        Ok(response
+            .validators
+            .into_iter()
+            .filter(|validator| validator.running_jito)
+            .map(|validator| validator.vote_account)
+            .collect())
+    }
+}
+
+impl Jito {
+    /// identities of validators running the Jito client, cached for
+    /// `LeaderScheduleConfig::validators_ttl`
+    pub async fn get_jito_validators(&self) -> Result<Arc<HashSet<Pubkey>>, JitoError> {
+        let tracker = &self.leader_tracker;
+        if let Some((fetched_at, identities)) = tracker.jito_identities.read().await.as_ref()
+            && fetched_at.elapsed() < tracker.config.validators_ttl
+        {
+            return Ok(identities.clone());
+        }
+        let jito_vote_accounts = tracker.fetch_jito_vote_accounts().await?;
+        let vote_accounts = self.solana.client_arc().get_vote_accounts().await?;
+        let identities: HashSet<Pubkey> = vote_accounts
+            .current
+            .iter()
+            .chain(vote_accounts.delinquent.iter())
+            .filter(|account| jito_vote_accounts.contains(&account.vote_pubkey))
+            .filter_map(|account| Pubkey::from_str(&account.node_pubkey).ok())
+            .collect();
+        let identities = Arc::new(identities);
+        *tracker.jito_identities.write().await = Some((Instant::now(), identities.clone()));
+        Ok(identities)
+    }
+
+    /// leaders of the current slot and the following `slots - 1` slots
+    pub async fn get_upcoming_leaders(&self, slots: u64) -> Result<Vec<Leader>, JitoError> {
+        let jito_validators = self.get_jito_validators().await?;
+        let client = self.solana.client_arc();
+        let current_slot = client.get_slot().await?;
+        let leaders = client
+            .get_slot_leaders(current_slot, slots.clamp(1, 5000))
+            .await?;
+        Ok(leaders
+            .into_iter()
+            .zip(current_slot..)
+            .map(|(pubkey, slot)| Leader {
+                pubkey,
+                slot,
+                is_jito: jito_validators.contains(&pubkey),
+            })
+            .collect())
+    }
+
+    /// first Jito leader within the next `within_slots` slots
+    pub async fn next_jito_leader(&self, within_slots: u64) -> Result<Option<Leader>, JitoError> {
+        Ok(self
+            .get_upcoming_leaders(within_slots.saturating_add(1))
+            .await?
+            .into_iter()
+            .find(|leader| leader.is_jito))
+    }
+
+    /// upcoming Jito leader slots, grouped by leader
+    pub async fn get_jito_leader_slots(
+        &self,
+        slots: u64,
+    ) -> Result<HashMap<Pubkey, Vec<u64>>, JitoError> {
+        let mut leader_slots: HashMap<Pubkey, Vec<u64>> = HashMap::new();
+        for leader in self.get_upcoming_leaders(slots).await? {
+            if leader.is_jito {
+                leader_slots
+                    .entry(leader.pubkey)
+                    .or_default()
+                    .push(leader.slot);
+            }
+        }
+        Ok(leader_slots)
+    }
+
+    /// wait until a Jito validator leads within `within_slots` slots;
+    /// `None` when `timeout` elapses first
+    pub async fn wait_for_jito_leader(
+        &self,
+        within_slots: u64,
+        timeout: Duration,
+    ) -> Result<Option<Leader>, JitoError> {
+        let started = Instant::now();
+        loop {
+            if let Some(leader) = self.next_jito_leader(within_slots).await? {
+                return Ok(Some(leader));
+            }
+            if started.elapsed() >= timeout {
+                return Ok(None);
+            }
+            sleep(SLOT_DURATION).await;
+        }
+    }
+}
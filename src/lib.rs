@@ -1,43 +1,79 @@
 use crate::client::{
-    BlockEngineClient, BundleClient, BundleStatus, HealthClient, HealthResponse, Leader,
+    BlockEngineClient, BundleClient, BundleStatus, InflightBundleStatus, JsonRpcTransport,
     MemPoolTransaction, StatisticsClient, StatsResponse, TipAccount, TipClient,
     TransactionsPoolClient, Validator, ValidatorsClient,
 };
 pub mod arbitrage;
+pub mod builder;
 pub mod bundle;
 pub mod client;
+pub mod config;
 pub mod copytrade;
+pub mod encoding;
+pub mod fanout;
 pub mod global;
+pub mod leader;
+pub mod rate_limit;
+pub mod remote_signer;
+pub mod retry;
+pub mod routing;
+pub mod signing;
+pub mod simulation;
+pub mod state;
+pub mod tip;
 pub mod tool;
 pub mod types;
+pub mod validation;
+pub mod watch;
 
+use crate::config::JitoConfig;
+use crate::fanout::FanoutClient;
+use crate::leader::LeaderTracker;
+use crate::rate_limit::RateLimiter;
+use crate::routing::RegionRouter;
+use crate::simulation::{SimulateBundleOptions, SimulationClient, SimulationReport};
+use crate::state::BundleState;
+use crate::tip::{TipAccountSelector, TipOracle, TipPercentile, TipStrategy};
 use crate::types::{JitoError, JitoResult};
-use serde::Deserialize;
 use solana_network_sdk::Solana;
 use solana_network_sdk::tool::token::safe_sol_to_lamports;
 use solana_sdk::{
-    message::Message, pubkey::Pubkey, signature::Keypair, signer::Signer, transaction::Transaction,
+    message::AddressLookupTableAccount, pubkey::Pubkey, transaction::VersionedTransaction,
 };
-use std::{fmt, str::FromStr, sync::Arc};
+use std::{str::FromStr, sync::Arc};
 use tokio::time::{Duration, sleep};
 
+/// how long tip accounts from getTipAccounts are reused
+const TIP_ACCOUNTS_REFRESH_INTERVAL: Duration = Duration::from_secs(300);
+
 pub struct Jito {
     bundle: BundleClient,
     tip: TipClient,
     block_engine: BlockEngineClient,
     validators: ValidatorsClient,
     transactions_pool: TransactionsPoolClient,
-    health: HealthClient,
     statistics: StatisticsClient,
+    simulation: SimulationClient,
+    rate_limiter: Arc<RateLimiter>,
+    fanout: FanoutClient,
+    router: Option<Arc<RegionRouter>>,
+    /// transport without routing, used to probe the regions
+    probe_transport: JsonRpcTransport,
+    tip_oracle: TipOracle,
+    leader_tracker: LeaderTracker,
+    tip_selector: TipAccountSelector,
     // solana client
     solana: Arc<Solana>,
+    config: JitoConfig,
 }
 
 pub struct ArbitrageConfig {
     pub min_profit_lamports: u64,
     pub max_slippage_bps: u16, // basis points
     pub max_retries: u32,
-    pub tip_percentage: f64, // percentage of profit to use as tip
+    pub tip_percentage: f64,       // percentage of profit to use as tip
+    pub priority_fee: Option<u64>, // micro-lamports per compute unit
+    pub compute_unit_limit: Option<u32>,
 }
 
 pub struct BackrunConfig {
@@ -47,78 +83,166 @@ pub struct BackrunConfig {
 }
 
 impl Jito {
-    pub fn new() -> JitoResult<Self, String> {
+    pub fn new(config: JitoConfig) -> JitoResult<Self> {
+        let rate_limiter = Arc::new(RateLimiter::new(config.rate_limit.clone()));
+        let client = config.http.build_client()?;
+        let unauthenticated = JsonRpcTransport::new(client.clone())
+            .with_retry_policy(config.retry.clone())
+            .with_rate_limiter(rate_limiter.clone());
+        // the auth UUID is only sent to the block engine, not to the simulation rpc
+        let probe_transport = unauthenticated.clone().with_auth(config.auth.clone());
+        let router = config
+            .routing
+            .clone()
+            .map(|routing| Arc::new(RegionRouter::new(config.block_engine_url(), routing)));
+        let transport = probe_transport.clone().with_router(router.clone());
         Ok(Self {
-            bundle: BundleClient::new(),
-            tip: TipClient::new(),
-            block_engine: BlockEngineClient::new(),
-            validators: ValidatorsClient::new(),
-            transactions_pool: TransactionsPoolClient::new(),
-            health: HealthClient::new(),
-            statistics: StatisticsClient::new(),
+            bundle: BundleClient::new(&config, transport.clone()),
+            tip: TipClient::new(&config, transport.clone()),
+            block_engine: BlockEngineClient::new(&config, transport.clone()),
+            validators: ValidatorsClient::new(&config, transport.clone()),
+            transactions_pool: TransactionsPoolClient::new(&config, transport.clone()),
+            statistics: StatisticsClient::new(&config, transport.clone()),
+            // region-pinned legs must not be rerouted to the best region
+            fanout: FanoutClient::new(&config, probe_transport.clone()),
+            simulation: SimulationClient::new(&config, unauthenticated),
+            rate_limiter,
+            router,
+            probe_transport,
+            tip_oracle: TipOracle::new(config.tip_oracle.clone())
+                .with_client(client.clone())
+                .with_retry_policy(config.retry.clone()),
+            leader_tracker: LeaderTracker::new(config.leader_schedule.clone())
+                .with_client(client)
+                .with_retry_policy(config.retry.clone()),
+            tip_selector: TipAccountSelector::new(config.tip_account_policy.clone()),
             solana: Arc::new(
-                Solana::new(solana_network_sdk::types::Mode::MAIN)
-                    .map_err(|e| JitoError::Error(format!("{:?}", e)))?,
+                Solana::new(config.solana_mode)
+                    .map_err(|e| JitoError::Config(format!("{:?}", e)))?,
             ),
+            config,
         })
     }
 
-    pub async fn health_check(&self) -> Result<HealthResponse, JitoError<String>> {
-        self.health
-            .check_health()
-            .await
-            .map_err(|e| JitoError::HealthError(e.to_string()))
+    pub fn config(&self) -> &JitoConfig {
+        &self.config
+    }
+
+    /// rate limiter shared by all sub-clients, exposes throttling metrics
+    pub fn rate_limiter(&self) -> &RateLimiter {
+        &self.rate_limiter
+    }
+
+    /// JSON-RPC probe of the block engine: a `getTipAccounts` round trip,
+    /// since the block engine serves no REST health endpoint
+    pub async fn health_check(&self) -> Result<(), JitoError> {
+        if self.tip.get_tip_accounts().await?.is_empty() {
+            return Err(JitoError::Unhealthy("no tip accounts".to_string()));
+        }
+        Ok(())
+    }
+
+    #[deprecated(note = "the block engine serves no REST `/api/v1/stats` endpoint")]
+    pub async fn get_statistics(&self) -> Result<StatsResponse, JitoError> {
+        self.statistics.get_statistics().await
+    }
+
+    pub async fn get_tip_accounts(&self) -> Result<Vec<TipAccount>, JitoError> {
+        self.tip.get_tip_accounts().await
     }
 
-    pub async fn get_statistics(&self) -> Result<StatsResponse, JitoError<String>> {
-        self.statistics
-            .get_statistics()
-            .await
-            .map_err(|e| JitoError::StatisticsError(e.to_string()))
+    /// tip account picked by the configured `TipAccountPolicy`
+    pub async fn get_optimal_tip_account(&self) -> Result<TipAccount, JitoError> {
+        self.refresh_tip_accounts_if_stale().await;
+        let pubkey = self.tip_selector.select()?;
+        Ok(TipAccount {
+            pubkey: pubkey.to_string(),
+        })
     }
 
-    pub async fn get_tip_accounts(&self) -> Result<Vec<TipAccount>, JitoError<String>> {
-        self.tip
+    pub fn tip_selector(&self) -> &TipAccountSelector {
+        &self.tip_selector
+    }
+
+    /// reload the tip accounts from `getTipAccounts`; on failure the selector
+    /// keeps its current (initially the built-in mainnet) accounts
+    pub async fn refresh_tip_accounts(&self) -> Result<(), JitoError> {
+        let accounts = self
             .get_tip_accounts()
-            .await
-            .map_err(|e| JitoError::TipError(e.to_string()))
+            .await?
+            .iter()
+            .map(|account| Pubkey::from_str(&account.pubkey))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| JitoError::Serialization(e.to_string()))?;
+        self.tip_selector.set_accounts(accounts);
+        Ok(())
+    }
+
+    /// refresh at most once per interval, failed attempts included
+    async fn refresh_tip_accounts_if_stale(&self) {
+        if self
+            .tip_selector
+            .claim_refresh(TIP_ACCOUNTS_REFRESH_INTERVAL)
+            && let Err(e) = self.refresh_tip_accounts().await
+        {
+            log::warn!("Failed to refresh tip accounts, using cached list: {}", e);
+        }
+    }
+
+    /// recommended tip in lamports at a landed tip percentile
+    pub async fn recommended_tip(&self, percentile: TipPercentile) -> u64 {
+        self.tip_oracle.recommended_tip(percentile).await
     }
 
-    pub async fn get_optimal_tip_account(&self) -> Result<TipAccount, JitoError<String>> {
-        self.tip
-            .get_optimal_tip_account()
-            .await
-            .map_err(|e| JitoError::TipError(e.to_string()))
+    /// tip account for a strategy: the strategy's account (validated),
+    /// otherwise one picked by the tip account selector
+    pub async fn resolve_tip_account(&self, tip: &TipStrategy) -> Result<Pubkey, JitoError> {
+        self.refresh_tip_accounts_if_stale().await;
+        match tip.tip_account {
+            Some(tip_account) => {
+                self.tip_selector.validate(&tip_account)?;
+                Ok(tip_account)
+            }
+            None => self.tip_selector.select(),
+        }
+    }
+
+    /// forward a single transaction through the block engine
+    pub async fn send_transaction<T: Into<VersionedTransaction>>(
+        &self,
+        transaction: T,
+    ) -> Result<String, JitoError> {
+        self.transactions_pool.send_transaction(transaction).await
     }
 
-    pub async fn get_network_congestion(&self) -> Result<f64, JitoError<String>> {
-        self.block_engine
-            .get_network_congestion()
-            .await
-            .map_err(|e| JitoError::BlockEngineError(e.to_string()))
+    #[deprecated(note = "the block engine serves no REST `/api/v1/block-engine` endpoint")]
+    pub async fn get_network_congestion(&self) -> Result<f64, JitoError> {
+        self.block_engine.get_network_congestion().await
     }
 
-    pub async fn get_current_leaders(&self) -> Result<Vec<Leader>, JitoError<String>> {
-        self.block_engine
-            .get_current_leaders()
-            .await
-            .map_err(|e| JitoError::BlockEngineError(e.to_string()))
+    #[deprecated(note = "the block engine serves no REST `/api/v1/validators` endpoint")]
+    pub async fn get_active_validators(&self) -> Result<Vec<Validator>, JitoError> {
+        self.validators.get_active_validators().await
     }
 
-    pub async fn get_active_validators(&self) -> Result<Vec<Validator>, JitoError<String>> {
-        self.validators
-            .get_active_validators()
-            .await
-            .map_err(|e| JitoError::ValidatorsError(e.to_string()))
+    #[deprecated(note = "the block engine serves no REST `/api/v1/transactions` endpoint")]
+    pub async fn get_mempool_transactions(&self) -> Result<Vec<MemPoolTransaction>, JitoError> {
+        self.transactions_pool.get_mempool_transactions().await
     }
 
-    pub async fn get_mempool_transactions(
+    /// simulate a bundle on the configured Jito-enabled rpc (`simulateBundle`),
+    /// or transaction by transaction on the solana rpc when none is configured;
+    /// see `simulate_bundle_locally` for the limits of the fallback
+    pub async fn simulate_bundle(
         &self,
-    ) -> Result<Vec<MemPoolTransaction>, JitoError<String>> {
-        self.transactions_pool
-            .get_mempool_transactions()
-            .await
-            .map_err(|e| JitoError::TransactionsPoolError(e.to_string()))
+        transactions: &[VersionedTransaction],
+        options: &SimulateBundleOptions,
+    ) -> Result<SimulationReport, JitoError> {
+        if self.simulation.is_available() {
+            self.simulation.simulate_bundle(transactions, options).await
+        } else {
+            self.simulate_bundle_locally(transactions, options).await
+        }
     }
 
     // ============== Bundle status monitoring ==============
@@ -126,33 +250,64 @@ impl Jito {
     pub async fn monitor_bundle_status(
         &self,
         bundle_id: &str,
-    ) -> Result<BundleStatus, JitoError<String>> {
-        self.bundle
-            .get_bundle_status(bundle_id)
-            .await
-            .map_err(|e| JitoError::BundleError(e.to_string()))
+    ) -> Result<InflightBundleStatus, JitoError> {
+        self.bundle.get_inflight_bundle_status(bundle_id).await
+    }
+
+    /// statuses of landed bundles (any number of ids, batched by 5)
+    pub async fn get_bundle_statuses(
+        &self,
+        bundle_ids: &[String],
+    ) -> Result<Vec<Option<BundleStatus>>, JitoError> {
+        self.bundle.get_bundle_statuses(bundle_ids).await
+    }
+
+    /// statuses of recently submitted bundles (any number of ids, batched by 5)
+    pub async fn get_inflight_bundle_statuses(
+        &self,
+        bundle_ids: &[String],
+    ) -> Result<Vec<InflightBundleStatus>, JitoError> {
+        self.bundle.get_inflight_bundle_statuses(bundle_ids).await
+    }
+
+    /// current lifecycle state of a bundle; `None` when the block engine
+    /// does not know the bundle (not yet indexed, or older than 5 minutes)
+    pub async fn get_bundle_state(
+        &self,
+        bundle_id: &str,
+    ) -> Result<Option<BundleState>, JitoError> {
+        if let Some(status) = self.bundle.get_bundle_status(bundle_id).await? {
+            return Ok(BundleState::from_statuses(None, Some(&status)));
+        }
+        let inflight = self.bundle.get_inflight_bundle_status(bundle_id).await?;
+        Ok(BundleState::from_statuses(Some(&inflight), None))
     }
 
+    /// poll until the bundle reaches `confirmed` or a terminal state,
+    /// returns the last observed state (non-terminal when retries run out)
     pub async fn wait_for_bundle_confirmation(
         &self,
         bundle_id: &str,
         max_retries: u32,
-    ) -> Result<bool, JitoError<String>> {
+    ) -> Result<BundleState, JitoError> {
+        let mut state = BundleState::Submitted;
         for _ in 0..max_retries {
-            match self.monitor_bundle_status(bundle_id).await {
-                Ok(status) => match status.status.as_str() {
-                    "confirmed" => return Ok(true),
-                    "failed" => return Ok(false),
-                    "expired" => return Ok(false),
-                    _ => {}
-                },
+            match self.get_bundle_state(bundle_id).await {
+                // stale observations (e.g. inflight lagging behind a landed status) are ignored
+                Ok(Some(next)) if state.can_transition_to(&next) => {
+                    state = next;
+                    if state.is_terminal() || matches!(state, BundleState::Confirmed { .. }) {
+                        return Ok(state);
+                    }
+                }
+                Ok(_) => {}
                 Err(e) => {
                     log::warn!("Failed to get bundle status: {}", e);
                 }
             }
             sleep(Duration::from_secs(1)).await;
         }
-        Ok(false)
+        Ok(state)
     }
 }
 
@@ -163,6 +318,8 @@ pub struct ArbitrageOpportunity {
     pub input_amount: u64,
     pub output_amount: u64,
     pub dexes: Vec<String>,
+    /// lookup tables used to compile the route into a v0 transaction
+    pub lookup_tables: Vec<AddressLookupTableAccount>,
 }
 
 impl Default for ArbitrageConfig {
@@ -172,6 +329,8 @@ impl Default for ArbitrageConfig {
             max_slippage_bps: 50,                                                 // 0.5%
             max_retries: 3,
             tip_percentage: 0.1, // 10% of profit
+            priority_fee: Some(50_000),
+            compute_unit_limit: None,
         }
     }
 }
@@ -0,0 +1,343 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tokio::time::sleep;
+
+use crate::config::Region;
+use crate::types::JitoError;
+
+/// Token bucket quota
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quota {
+    /// tokens added per second; zero or less blocks the method
+    pub per_second: f64,
+    /// bucket capacity, i.e. the largest burst
+    pub burst: u32,
+}
+
+impl Quota {
+    pub fn per_second(per_second: f64) -> Self {
+        Self {
+            per_second,
+            burst: 1,
+        }
+    }
+
+    pub fn with_burst(mut self, burst: u32) -> Self {
+        self.burst = burst.max(1);
+        self
+    }
+
+    /// quota that never grants a call
+    pub fn blocked() -> Self {
+        Self::per_second(0.0)
+    }
+
+    /// the rate is zero, negative or NaN, so no call is ever granted
+    pub fn is_blocked(&self) -> bool {
+        self.per_second.is_nan() || self.per_second <= 0.0
+    }
+}
+
+/// What happens to a call that exceeds its quota
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RateLimitMode {
+    /// wait until a token is available
+    #[default]
+    Queue,
+    /// fail immediately with `JitoError::Throttled`
+    FailFast,
+}
+
+/// Client-side rate limits. Calls are keyed by region (or base url for
+/// custom endpoints) and method; JSON-RPC calls use the method name
+/// (`sendBundle`), REST calls the api path (`/api/v1/health`).
+#[derive(Debug, Clone)]
+pub struct RateLimitConfig {
+    pub mode: RateLimitMode,
+    /// quota of methods without their own entry, unlimited when `None`
+    pub default_quota: Option<Quota>,
+    pub method_quotas: HashMap<String, Quota>,
+    /// per-region overrides of method quotas
+    pub region_quotas: HashMap<Region, HashMap<String, Quota>>,
+}
+
+impl Default for RateLimitConfig {
+    /// the block engine default of 1 submission per second per region
+    fn default() -> Self {
+        Self {
+            mode: RateLimitMode::default(),
+            default_quota: None,
+            method_quotas: HashMap::from([
+                ("sendBundle".to_string(), Quota::per_second(1.0)),
+                ("sendTransaction".to_string(), Quota::per_second(1.0)),
+            ]),
+            region_quotas: HashMap::new(),
+        }
+    }
+}
+
+impl RateLimitConfig {
+    /// no client-side limits
+    pub fn unlimited() -> Self {
+        Self {
+            mode: RateLimitMode::default(),
+            default_quota: None,
+            method_quotas: HashMap::new(),
+            region_quotas: HashMap::new(),
+        }
+    }
+
+    pub fn with_mode(mut self, mode: RateLimitMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn with_default_quota(mut self, quota: Option<Quota>) -> Self {
+        self.default_quota = quota;
+        self
+    }
+
+    pub fn with_method_quota(mut self, method: impl Into<String>, quota: Quota) -> Self {
+        self.method_quotas.insert(method.into(), quota);
+        self
+    }
+
+    pub fn with_region_quota(
+        mut self,
+        region: Region,
+        method: impl Into<String>,
+        quota: Quota,
+    ) -> Self {
+        self.region_quotas
+            .entry(region)
+            .or_default()
+            .insert(method.into(), quota);
+        self
+    }
+
+    /// quota of a method in a region
+    pub fn quota(&self, region: Option<Region>, method: &str) -> Option<Quota> {
+        region
+            .and_then(|region| self.region_quotas.get(&region)?.get(method))
+            .or_else(|| self.method_quotas.get(method))
+            .copied()
+            .or(self.default_quota)
+    }
+}
+
+/// Throttling counters of one method
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ThrottleStats {
+    /// calls that had to wait for a token
+    pub queued: u64,
+    /// calls rejected in fail-fast mode
+    pub rejected: u64,
+    /// total time spent waiting
+    pub wait: Duration,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    quota: Quota,
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    fn new(quota: Quota) -> Self {
+        // `burst` is public, so a zero can bypass `with_burst`; an empty
+        // bucket would never refill
+        let quota = quota.with_burst(quota.burst);
+        Self {
+            quota,
+            tokens: quota.burst as f64,
+            updated: Instant::now(),
+        }
+    }
+
+    /// take a token, or return how long until one is available
+    fn try_take(&mut self) -> Result<(), Duration> {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.quota.per_second).min(self.quota.burst as f64);
+        self.updated = now;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            return Ok(());
+        }
+        let missing = 1.0 - self.tokens;
+        Err(Duration::try_from_secs_f64(missing / self.quota.per_second).unwrap_or(Duration::MAX))
+    }
+}
+
+#[derive(Debug, Default)]
+struct LimiterState {
+    buckets: HashMap<(String, String), Bucket>,
+    stats: HashMap<String, ThrottleStats>,
+}
+
+/// Token-bucket rate limiter shared by all sub-clients of a `Jito`
+#[derive(Debug)]
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    state: Mutex<LimiterState>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(LimiterState::default()),
+        }
+    }
+
+    pub fn config(&self) -> &RateLimitConfig {
+        &self.config
+    }
+
+    /// wait for (or, in fail-fast mode, demand) a token for a call of
+    /// `method` to `url`
+    pub async fn acquire(&self, url: &str, method: &str) -> Result<(), JitoError> {
+        let region = Region::ALL
+            .into_iter()
+            .find(|region| url.starts_with(region.url()));
+        let Some(quota) = self.config.quota(region, method) else {
+            return Ok(());
+        };
+        if quota.is_blocked() {
+            // waiting would never end, so even queued calls fail
+            let mut state = self.state.lock().unwrap();
+            state.stats.entry(method.to_string()).or_default().rejected += 1;
+            return Err(JitoError::Throttled {
+                method: method.to_string(),
+                wait: Duration::MAX,
+            });
+        }
+        let key = (
+            region.map_or_else(|| base_url(url).to_string(), |region| region.to_string()),
+            method.to_string(),
+        );
+        let mut queued = false;
+        loop {
+            let wait = {
+                let mut state = self.state.lock().unwrap();
+                let wait = match state
+                    .buckets
+                    .entry(key.clone())
+                    .or_insert_with(|| Bucket::new(quota))
+                    .try_take()
+                {
+                    Ok(()) => return Ok(()),
+                    Err(wait) => wait,
+                };
+                let stats = state.stats.entry(method.to_string()).or_default();
+                match self.config.mode {
+                    RateLimitMode::FailFast => {
+                        stats.rejected += 1;
+                        return Err(JitoError::Throttled {
+                            method: method.to_string(),
+                            wait,
+                        });
+                    }
+                    RateLimitMode::Queue => {
+                        if !queued {
+                            stats.queued += 1;
+                            queued = true;
+                        }
+                        stats.wait = stats.wait.saturating_add(wait);
+                    }
+                }
+                wait
+            };
+            log::debug!("Rate limited {} on {}, waiting {:?}", method, key.0, wait);
+            sleep(wait).await;
+        }
+    }
+
+    /// throttling counters per method
+    pub fn metrics(&self) -> HashMap<String, ThrottleStats> {
+        self.state.lock().unwrap().stats.clone()
+    }
+
+    /// throttling counters summed over all methods
+    pub fn total_metrics(&self) -> ThrottleStats {
+        self.metrics()
+            .values()
+            .fold(ThrottleStats::default(), |total, stats| ThrottleStats {
+                queued: total.queued + stats.queued,
+                rejected: total.rejected + stats.rejected,
+                wait: total.wait.saturating_add(stats.wait),
+            })
+    }
+}
+
+/// `scheme://host[:port]` of a url
+fn base_url(url: &str) -> &str {
+    let host_start = url.find("://").map_or(0, |index| index + 3);
+    match url[host_start..].find('/') {
+        Some(index) => &url[..host_start + index],
+        None => url,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn blocked_quota_throttles_instead_of_panicking() {
+        for per_second in [0.0, -1.0, f64::NAN] {
+            let mut config = RateLimitConfig {
+                mode: RateLimitMode::Queue,
+                ..RateLimitConfig::default()
+            };
+            config
+                .method_quotas
+                .insert("sendBundle".to_string(), Quota::per_second(per_second));
+            let limiter = RateLimiter::new(config);
+            let result = limiter
+                .acquire("http://127.0.0.1:8899/api/v1/bundles", "sendBundle")
+                .await;
+            assert!(matches!(result, Err(JitoError::Throttled { .. })));
+            assert_eq!(limiter.metrics()["sendBundle"].rejected, 1);
+        }
+    }
+
+    #[test]
+    fn tiny_rate_does_not_overflow() {
+        let mut bucket = Bucket::new(Quota::per_second(f64::MIN_POSITIVE));
+        assert!(bucket.try_take().is_ok());
+        assert_eq!(bucket.try_take(), Err(Duration::MAX));
+    }
+
+    #[test]
+    fn zero_burst_still_grants_a_call() {
+        let mut bucket = Bucket::new(Quota {
+            per_second: 1.0,
+            burst: 0,
+        });
+        assert!(bucket.try_take().is_ok());
+    }
+
+    #[tokio::test]
+    async fn queued_wait_saturates() {
+        let config = RateLimitConfig::unlimited()
+            .with_method_quota("sendBundle", Quota::per_second(f64::MIN_POSITIVE));
+        let limiter = RateLimiter::new(config);
+        let url = "http://127.0.0.1:8899/api/v1/bundles";
+        limiter.acquire(url, "sendBundle").await.unwrap();
+        for _ in 0..2 {
+            let queued = limiter.acquire(url, "sendBundle");
+            assert!(
+                tokio::time::timeout(Duration::from_millis(10), queued)
+                    .await
+                    .is_err()
+            );
+        }
+        let stats = limiter.total_metrics();
+        assert_eq!(stats.queued, 2);
+        assert_eq!(stats.wait, Duration::MAX);
+    }
+}
@@ -0,0 +1,308 @@
+use std::str::FromStr;
+
+use base64::{Engine, prelude::BASE64_STANDARD};
+use futures::future::BoxFuture;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+
+use crate::client::fetch_json;
+use crate::config::HttpConfig;
+use crate::retry::{Idempotency, RetryPolicy};
+use crate::types::JitoError;
+
+#[cfg(any(test, feature = "test-utils"))]
+pub use local::LocalSignerService;
+
+/// Signer whose key lives out of process (HSM, KMS, wallet service).
+/// Used next to in-memory `Signer`s, e.g. `BundleBuilder::with_async_signer`.
+pub trait AsyncSigner: Send + Sync {
+    fn pubkey(&self) -> Pubkey;
+
+    /// sign a serialized transaction message
+    fn sign<'a>(&'a self, message: &'a [u8]) -> BoxFuture<'a, Result<Signature, JitoError>>;
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SignRequest {
+    pubkey: String,
+    /// base64 serialized message
+    message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SignResponse {
+    /// base58 signature
+    signature: String,
+}
+
+/// Signs through an HTTP signing service: `POST <url>` with
+/// `{"pubkey", "message"}` (message base64) answered by `{"signature"}`
+/// (base58). Returned signatures are verified before use.
+#[derive(Debug, Clone)]
+pub struct HttpSigner {
+    client: Client,
+    url: String,
+    pubkey: Pubkey,
+    retry: RetryPolicy,
+}
+
+impl HttpSigner {
+    /// signer with a client built from `HttpConfig::default()`, so a stalled
+    /// signing service times out instead of hanging the bundle build
+    pub fn new(url: impl Into<String>, pubkey: Pubkey) -> Result<Self, JitoError> {
+        Ok(Self {
+            client: HttpConfig::default().build_client()?,
+            url: url.into(),
+            pubkey,
+            retry: RetryPolicy::default(),
+        })
+    }
+
+    /// use a shared HTTP client
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// signing is deterministic, so failed requests are retried
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    async fn request_signature(&self, message: &[u8]) -> Result<Signature, JitoError> {
+        let request = SignRequest {
+            pubkey: self.pubkey.to_string(),
+            message: BASE64_STANDARD.encode(message),
+        };
+        let response: SignResponse = self
+            .retry
+            .run(Idempotency::Idempotent, || {
+                fetch_json(self.client.post(&self.url).json(&request))
+            })
+            .await?;
+        let signature = Signature::from_str(&response.signature)
+            .map_err(|e| JitoError::Transaction(format!("invalid remote signature: {}", e)))?;
+        if !signature.verify(self.pubkey.as_ref(), message) {
+            return Err(JitoError::Transaction(format!(
+                "remote signature does not match {}",
+                self.pubkey
+            )));
+        }
+        Ok(signature)
+    }
+}
+
+impl AsyncSigner for HttpSigner {
+    fn pubkey(&self) -> Pubkey {
+        self.pubkey
+    }
+
+    fn sign<'a>(&'a self, message: &'a [u8]) -> BoxFuture<'a, Result<Signature, JitoError>> {
+        Box::pin(self.request_signature(message))
+    }
+}
+
+// ============== local stand-in service ==============
+
+#[cfg(any(test, feature = "test-utils"))]
+mod local {
+    use std::sync::Arc;
+
+    use base64::{Engine, prelude::BASE64_STANDARD};
+    use solana_sdk::pubkey::Pubkey;
+    use solana_sdk::signature::Keypair;
+    use solana_sdk::signer::Signer;
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio::task::JoinHandle;
+
+    use super::{HttpSigner, SignRequest, SignResponse};
+    use crate::types::JitoError;
+
+    /// upper bound of a request, headers included
+    const MAX_REQUEST_SIZE: u64 = 64 * 1024;
+
+    /// Keypair-backed signing service speaking the `HttpSigner` protocol on
+    /// a local port; stands in for a real signing service in tests. Only
+    /// built for tests or with the `test-utils` feature.
+    #[derive(Clone)]
+    pub struct LocalSignerService {
+        keypair: Arc<Keypair>,
+    }
+
+    impl LocalSignerService {
+        pub fn new(keypair: Keypair) -> Self {
+            Self {
+                keypair: Arc::new(keypair),
+            }
+        }
+
+        pub fn pubkey(&self) -> Pubkey {
+            self.keypair.pubkey()
+        }
+
+        /// listen on `addr` (e.g. `127.0.0.1:0`); returns an `HttpSigner`
+        /// pointed at the service and the server task
+        pub async fn spawn(self, addr: &str) -> Result<(HttpSigner, JoinHandle<()>), JitoError> {
+            let listener = TcpListener::bind(addr)
+                .await
+                .map_err(|e| JitoError::Config(format!("failed to bind {}: {}", addr, e)))?;
+            let local_addr = listener
+                .local_addr()
+                .map_err(|e| JitoError::Config(e.to_string()))?;
+            let signer = HttpSigner::new(format!("http://{}/sign", local_addr), self.pubkey())?;
+            let handle = tokio::spawn(async move {
+                loop {
+                    let stream = match listener.accept().await {
+                        Ok((stream, _)) => stream,
+                        Err(e) => {
+                            log::warn!("Local signer accept failed: {}", e);
+                            continue;
+                        }
+                    };
+                    let service = self.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = service.handle(stream).await {
+                            log::debug!("Local signer connection failed: {}", e);
+                        }
+                    });
+                }
+            });
+            Ok((signer, handle))
+        }
+
+        /// serve one request and close the connection; reads at most
+        /// `MAX_REQUEST_SIZE` bytes
+        async fn handle(&self, stream: TcpStream) -> std::io::Result<()> {
+            let mut reader = BufReader::new(stream.take(MAX_REQUEST_SIZE));
+            let mut content_length = 0;
+            let mut line = String::new();
+            loop {
+                line.clear();
+                if reader.read_line(&mut line).await? == 0 || line.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':')
+                    && name.eq_ignore_ascii_case("content-length")
+                {
+                    content_length = value.trim().parse().unwrap_or(0);
+                }
+            }
+            let (status, body) = if content_length as u64 > MAX_REQUEST_SIZE {
+                (
+                    "413 Payload Too Large",
+                    serde_json::json!({ "error": "request too large" }).to_string(),
+                )
+            } else {
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).await?;
+                match self.sign_request(&body) {
+                    Ok(response) => ("200 OK", response),
+                    Err(e) => (
+                        "400 Bad Request",
+                        serde_json::json!({ "error": e }).to_string(),
+                    ),
+                }
+            };
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            let mut stream = reader.into_inner().into_inner();
+            stream.write_all(response.as_bytes()).await?;
+            stream.shutdown().await
+        }
+
+        fn sign_request(&self, body: &[u8]) -> Result<String, String> {
+            let request: SignRequest = serde_json::from_slice(body).map_err(|e| e.to_string())?;
+            if request.pubkey != self.pubkey().to_string() {
+                return Err(format!("unknown key {}", request.pubkey));
+            }
+            let message = BASE64_STANDARD
+                .decode(&request.message)
+                .map_err(|e| e.to_string())?;
+            let response = SignResponse {
+                signature: self.keypair.sign_message(&message).to_string(),
+            };
+            serde_json::to_string(&response).map_err(|e| e.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::hash::Hash;
+    use solana_sdk::instruction::AccountMeta;
+    use solana_sdk::message::Instruction;
+    use solana_sdk::signature::Keypair;
+    use solana_sdk::signer::Signer;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+
+    use super::*;
+    use crate::Jito;
+    use crate::bundle::Bundle;
+    use crate::config::JitoConfig;
+    use crate::signing::missing_signers;
+
+    #[tokio::test]
+    async fn builder_signs_through_local_signer_service() {
+        let service = LocalSignerService::new(Keypair::new());
+        let payer = service.pubkey();
+        let (remote, server) = service.spawn("127.0.0.1:0").await.unwrap();
+        let cosigner = Keypair::new();
+        let instruction = Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[1],
+            vec![
+                AccountMeta::new(payer, true),
+                AccountMeta::new_readonly(cosigner.pubkey(), true),
+            ],
+        );
+        let bundle = Bundle::new(Jito::new(JitoConfig::default()).unwrap());
+        let transactions = bundle
+            .builder()
+            .with_payer(payer)
+            .with_async_signer(&remote)
+            .with_signer(&cosigner)
+            .with_tip(None)
+            .with_balance_check(false)
+            .with_recent_blockhash(Hash::new_unique())
+            .add_instructions(vec![instruction.clone()])
+            .add_instructions(vec![instruction])
+            .build()
+            .await
+            .unwrap();
+        server.abort();
+        assert_eq!(transactions.len(), 2);
+        for transaction in &transactions {
+            assert_eq!(transaction.message.static_account_keys()[0], payer);
+            assert!(missing_signers(transaction).is_empty());
+            assert!(transaction.verify_with_results().into_iter().all(|ok| ok));
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_oversized_content_length() {
+        let service = LocalSignerService::new(Keypair::new());
+        let (remote, server) = service.spawn("127.0.0.1:0").await.unwrap();
+        let addr = remote
+            .url
+            .trim_start_matches("http://")
+            .trim_end_matches("/sign");
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"POST /sign HTTP/1.1\r\nContent-Length: 18446744073709551615\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        server.abort();
+        assert!(response.starts_with("HTTP/1.1 413"), "{}", response);
+    }
+}
@@ -0,0 +1,232 @@
+use std::future::Future;
+use std::time::Duration;
+
+use rand::Rng;
+use tokio::time::sleep;
+
+use crate::types::JitoError;
+
+/// Whether repeating a request can have unwanted side effects
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Idempotency {
+    /// queries; retried on any retryable error
+    Idempotent,
+    /// submissions (`sendBundle`, `sendTransaction`); only retried when the
+    /// request never reached the server
+    NonIdempotent,
+}
+
+impl Idempotency {
+    /// idempotency of a JSON-RPC method
+    pub fn of_method(method: &str) -> Self {
+        match method {
+            "sendBundle" | "sendTransaction" => Idempotency::NonIdempotent,
+            _ => Idempotency::Idempotent,
+        }
+    }
+}
+
+/// Exponential backoff with jitter for Jito HTTP calls
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// retries after the first attempt, 0 disables retrying
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub multiplier: f64,
+    /// fraction of each delay that is randomized, between 0 and 1
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+            multiplier: 2.0,
+            jitter: 0.5,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// policy that never retries
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    pub fn with_backoff(mut self, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// whether `error` may be retried for a request of the given idempotency
+    pub fn should_retry(&self, error: &JitoError, idempotency: Idempotency) -> bool {
+        match idempotency {
+            Idempotency::Idempotent => error.is_retryable(),
+            Idempotency::NonIdempotent => {
+                matches!(error, JitoError::Transport(e) if e.is_connect())
+            }
+        }
+    }
+
+    /// delay before retry number `attempt` (0-based); a server supplied
+    /// `Retry-After` takes precedence over the computed backoff
+    pub fn delay(&self, attempt: u32, error: &JitoError) -> Duration {
+        if let Some(retry_after) = error.retry_after() {
+            return retry_after;
+        }
+        // computed in f64 seconds and clamped before converting back, so large
+        // attempts or odd multipliers can't overflow `Duration`
+        let multiplier = if self.multiplier.is_finite() {
+            self.multiplier.max(1.0)
+        } else {
+            1.0
+        };
+        let max_secs = self.max_backoff.as_secs_f64();
+        let secs = self.initial_backoff.as_secs_f64()
+            * multiplier.powi(attempt.min(i32::MAX as u32) as i32);
+        let backoff = if secs.is_finite() {
+            secs.min(max_secs)
+        } else {
+            max_secs
+        };
+        let jitter = if self.jitter.is_nan() {
+            0.0
+        } else {
+            self.jitter.clamp(0.0, 1.0)
+        };
+        let jitter = rand::thread_rng().gen_range(0.0..=jitter);
+        Duration::from_secs_f64(backoff * (1.0 - jitter))
+    }
+
+    /// run `operation` until it succeeds, fails with a non-retryable error
+    /// or the retries are used up
+    pub async fn run<T, F, Fut>(
+        &self,
+        idempotency: Idempotency,
+        mut operation: F,
+    ) -> Result<T, JitoError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, JitoError>>,
+    {
+        let mut attempt = 0;
+        loop {
+            match operation().await {
+                Err(e) if attempt < self.max_retries && self.should_retry(&e, idempotency) => {
+                    let delay = self.delay(attempt, &e);
+                    log::debug!(
+                        "Retrying in {:?} ({}/{}): {}",
+                        delay,
+                        attempt + 1,
+                        self.max_retries,
+                        e
+                    );
+                    sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rate_limited() -> JitoError {
+        JitoError::RateLimited { retry_after: None }
+    }
+
+    #[test]
+    fn submissions_are_not_idempotent() {
+        assert_eq!(
+            Idempotency::of_method("sendBundle"),
+            Idempotency::NonIdempotent
+        );
+        assert_eq!(
+            Idempotency::of_method("sendTransaction"),
+            Idempotency::NonIdempotent
+        );
+        assert_eq!(
+            Idempotency::of_method("getBundleStatuses"),
+            Idempotency::Idempotent
+        );
+        assert_eq!(
+            Idempotency::of_method("getTipAccounts"),
+            Idempotency::Idempotent
+        );
+    }
+
+    #[test]
+    fn only_idempotent_requests_retry_server_errors() {
+        let policy = RetryPolicy::default();
+        assert!(policy.should_retry(&rate_limited(), Idempotency::Idempotent));
+        assert!(!policy.should_retry(&rate_limited(), Idempotency::NonIdempotent));
+    }
+
+    #[test]
+    fn backoff_grows_and_caps() {
+        let policy = RetryPolicy::default()
+            .with_backoff(Duration::from_millis(100), Duration::from_secs(1))
+            .with_jitter(0.0);
+        assert_eq!(policy.delay(0, &rate_limited()), Duration::from_millis(100));
+        assert_eq!(policy.delay(2, &rate_limited()), Duration::from_millis(400));
+        assert_eq!(policy.delay(10, &rate_limited()), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn large_attempts_do_not_overflow() {
+        let policy = RetryPolicy::default().with_max_retries(100);
+        for attempt in [68, 100, u32::MAX] {
+            assert!(policy.delay(attempt, &rate_limited()) <= policy.max_backoff);
+        }
+        let policy = RetryPolicy {
+            multiplier: f64::INFINITY,
+            ..RetryPolicy::default()
+        };
+        assert!(policy.delay(3, &rate_limited()) <= policy.max_backoff);
+    }
+
+    #[test]
+    fn nan_jitter_is_ignored() {
+        let policy = RetryPolicy {
+            jitter: f64::NAN,
+            ..RetryPolicy::default()
+        };
+        assert_eq!(policy.delay(0, &rate_limited()), policy.initial_backoff);
+    }
+
+    #[test]
+    fn retry_after_takes_precedence() {
+        let error = JitoError::RateLimited {
+            retry_after: Some(Duration::from_secs(7)),
+        };
+        assert_eq!(
+            RetryPolicy::default().delay(0, &error),
+            Duration::from_secs(7)
+        );
+    }
+}
@@ -0,0 +1,295 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use futures::future::join_all;
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, timeout};
+
+use crate::Jito;
+use crate::client::JsonRpcTransport;
+use crate::config::Region;
+use crate::global::BUNDLE_PATH;
+use crate::retry::RetryPolicy;
+use crate::types::JitoError;
+
+/// Latency-aware routing across block engine regions
+#[derive(Debug, Clone)]
+pub struct RoutingConfig {
+    /// candidate regions, in order of preference when latencies tie
+    pub regions: Vec<Region>,
+    pub probe_interval: Duration,
+    pub probe_timeout: Duration,
+    /// weight of the newest sample in the moving latency average
+    pub ewma_alpha: f64,
+    /// consecutive failures after which a region is considered unhealthy
+    pub failure_threshold: u32,
+}
+
+impl Default for RoutingConfig {
+    fn default() -> Self {
+        Self {
+            regions: Region::MAINNET_REGIONS.to_vec(),
+            probe_interval: Duration::from_secs(30),
+            probe_timeout: Duration::from_secs(2),
+            ewma_alpha: 0.3,
+            failure_threshold: 2,
+        }
+    }
+}
+
+impl RoutingConfig {
+    pub fn new(regions: impl Into<Vec<Region>>) -> Self {
+        Self {
+            regions: regions.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_probe_interval(mut self, probe_interval: Duration) -> Self {
+        self.probe_interval = probe_interval;
+        self
+    }
+
+    pub fn with_probe_timeout(mut self, probe_timeout: Duration) -> Self {
+        self.probe_timeout = probe_timeout;
+        self
+    }
+
+    pub fn with_ewma_alpha(mut self, ewma_alpha: f64) -> Self {
+        self.ewma_alpha = ewma_alpha.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_failure_threshold(mut self, failure_threshold: u32) -> Self {
+        self.failure_threshold = failure_threshold.max(1);
+        self
+    }
+}
+
+/// Observed state of one region
+#[derive(Debug, Clone)]
+pub struct RegionStats {
+    pub region: Region,
+    /// moving average of the probe round-trip time, `None` until probed
+    pub latency: Option<Duration>,
+    pub healthy: bool,
+    pub consecutive_failures: u32,
+    pub last_probe: Option<Instant>,
+}
+
+impl RegionStats {
+    fn new(region: Region) -> Self {
+        Self {
+            region,
+            latency: None,
+            healthy: true,
+            consecutive_failures: 0,
+            last_probe: None,
+        }
+    }
+}
+
+/// Routes block engine requests to the fastest healthy region. Requests
+/// addressed to the configured endpoint are rewritten to the best region;
+/// failures mark a region unhealthy so the next retry fails over.
+#[derive(Debug)]
+pub struct RegionRouter {
+    /// base url requests are addressed to
+    primary: String,
+    config: RoutingConfig,
+    stats: Mutex<Vec<RegionStats>>,
+}
+
+impl RegionRouter {
+    pub fn new(primary: impl Into<String>, config: RoutingConfig) -> Self {
+        let stats = config
+            .regions
+            .iter()
+            .copied()
+            .map(RegionStats::new)
+            .collect();
+        Self {
+            primary: primary.into(),
+            config,
+            stats: Mutex::new(stats),
+        }
+    }
+
+    pub fn config(&self) -> &RoutingConfig {
+        &self.config
+    }
+
+    /// regions ordered best first: healthy before unhealthy, then by latency,
+    /// unprobed regions last
+    pub fn ranking(&self) -> Vec<RegionStats> {
+        let mut ranking = self.stats.lock().unwrap().clone();
+        ranking.sort_by_key(|stats| (!stats.healthy, stats.latency.is_none(), stats.latency));
+        ranking
+    }
+
+    /// fastest healthy region that has been probed
+    pub fn best_region(&self) -> Option<Region> {
+        self.ranking()
+            .into_iter()
+            .find(|stats| stats.healthy && stats.latency.is_some())
+            .map(|stats| stats.region)
+    }
+
+    /// `url` rewritten to the best region when it targets the primary endpoint
+    pub fn route(&self, url: &str) -> (String, Option<Region>) {
+        match (url.strip_prefix(&self.primary), self.best_region()) {
+            (Some(path), Some(region)) => (format!("{}{}", region.url(), path), Some(region)),
+            _ => (url.to_string(), None),
+        }
+    }
+
+    fn update(&self, region: Region, update: impl FnOnce(&mut RegionStats, &RoutingConfig)) {
+        let mut stats = self.stats.lock().unwrap();
+        if let Some(stats) = stats.iter_mut().find(|stats| stats.region == region) {
+            update(stats, &self.config);
+        }
+    }
+
+    /// record a successful probe round trip
+    pub fn record_latency(&self, region: Region, latency: Duration) {
+        self.update(region, |stats, config| {
+            // `ewma_alpha` is public, so clamp it here as well
+            let alpha = if config.ewma_alpha.is_nan() {
+                1.0
+            } else {
+                config.ewma_alpha.clamp(0.0, 1.0)
+            };
+            stats.latency = Some(match stats.latency {
+                Some(average) => average.mul_f64(1.0 - alpha) + latency.mul_f64(alpha),
+                None => latency,
+            });
+            stats.last_probe = Some(Instant::now());
+            stats.consecutive_failures = 0;
+            stats.healthy = true;
+        });
+    }
+
+    pub fn record_success(&self, region: Region) {
+        self.update(region, |stats, _| {
+            stats.consecutive_failures = 0;
+            stats.healthy = true;
+        });
+    }
+
+    pub fn record_failure(&self, region: Region) {
+        self.update(region, |stats, config| {
+            stats.consecutive_failures += 1;
+            if stats.consecutive_failures >= config.failure_threshold {
+                stats.healthy = false;
+            }
+        });
+    }
+
+    /// record the outcome of a routed request; only errors that point at
+    /// the region (not at the request or the rate limit) count as failures
+    pub(crate) fn record_result<T>(&self, region: Region, result: &Result<T, JitoError>) {
+        match result {
+            Ok(_) => self.record_success(region),
+            Err(JitoError::RateLimited { .. } | JitoError::Throttled { .. }) => {}
+            Err(e) if e.is_retryable() => {
+                log::warn!("Region {} failed: {}", region, e);
+                self.record_failure(region);
+            }
+            Err(_) => {}
+        }
+    }
+
+    /// probe every region once with a `getTipAccounts` round trip
+    pub async fn probe(&self, transport: &JsonRpcTransport) {
+        let no_retry = RetryPolicy::none();
+        let probes = self.config.regions.iter().map(|region| {
+            let url = format!("{}{}", region.url(), BUNDLE_PATH);
+            let no_retry = &no_retry;
+            async move {
+                let started = Instant::now();
+                let call = transport.call_with_retry::<_, Vec<String>>(
+                    &url,
+                    "getTipAccounts",
+                    [(); 0],
+                    no_retry,
+                );
+                let ok = match timeout(self.config.probe_timeout, call).await {
+                    Ok(Ok(_)) => true,
+                    Ok(Err(e)) => {
+                        log::debug!("Probe of {} failed: {}", region, e);
+                        false
+                    }
+                    Err(_) => {
+                        log::debug!("Probe of {} timed out", region);
+                        false
+                    }
+                };
+                (*region, ok, started.elapsed())
+            }
+        });
+        for (region, ok, latency) in join_all(probes).await {
+            if ok {
+                self.record_latency(region, latency);
+            } else {
+                self.record_failure(region);
+            }
+        }
+    }
+
+    /// probe all regions every `probe_interval` until the handle is aborted
+    pub fn spawn_probing(self: Arc<Self>, transport: JsonRpcTransport) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                self.probe(&transport).await;
+                log::debug!("Region ranking: {:?}", self.ranking());
+                sleep(self.config.probe_interval).await;
+            }
+        })
+    }
+}
+
+impl Jito {
+    /// current region ranking, best first; empty when routing is disabled
+    pub fn region_ranking(&self) -> Vec<RegionStats> {
+        self.router
+            .as_ref()
+            .map(|router| router.ranking())
+            .unwrap_or_default()
+    }
+
+    /// probe all routing regions once
+    pub async fn probe_regions(&self) {
+        if let Some(router) = &self.router {
+            router.probe(&self.probe_transport).await;
+        }
+    }
+
+    /// start periodic probing in the background; must be called within a
+    /// tokio runtime. `None` when routing is disabled.
+    pub fn spawn_region_probing(&self) -> Option<JoinHandle<()>> {
+        let router = self.router.clone()?;
+        Some(router.spawn_probing(self.probe_transport.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn out_of_range_alpha_is_clamped() {
+        for (alpha, expected) in [(1.5, 200), (-0.5, 100), (f64::NAN, 200)] {
+            let config = RoutingConfig {
+                ewma_alpha: alpha,
+                ..RoutingConfig::new([Region::Frankfurt])
+            };
+            let router = RegionRouter::new(Region::Frankfurt.url(), config);
+            router.record_latency(Region::Frankfurt, Duration::from_millis(100));
+            router.record_latency(Region::Frankfurt, Duration::from_millis(200));
+            assert_eq!(
+                router.ranking()[0].latency,
+                Some(Duration::from_millis(expected))
+            );
+        }
+    }
+}
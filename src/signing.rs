@@ -0,0 +1,88 @@
+use futures::future::join_all;
+use solana_sdk::message::VersionedMessage;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::VersionedTransaction;
+
+use crate::remote_signer::AsyncSigner;
+use crate::types::JitoError;
+
+/// keys that must sign a message, fee payer first
+pub fn required_signers(message: &VersionedMessage) -> &[Pubkey] {
+    let keys = message.static_account_keys();
+    let required = message.header().num_required_signatures as usize;
+    &keys[..required.min(keys.len())]
+}
+
+/// required signers whose signature is still empty
+pub fn missing_signers(transaction: &VersionedTransaction) -> Vec<Pubkey> {
+    required_signers(&transaction.message)
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| {
+            transaction
+                .signatures
+                .get(*index)
+                .is_none_or(|signature| *signature == Signature::default())
+        })
+        .map(|(_, pubkey)| *pubkey)
+        .collect()
+}
+
+/// Sign with every given signer the message requires, leaving the other
+/// signature slots untouched; other parties can add theirs later.
+/// Signers the message does not require are ignored.
+pub fn partial_sign(
+    transaction: &mut VersionedTransaction,
+    signers: &[&dyn Signer],
+) -> Result<(), JitoError> {
+    let required = required_signers(&transaction.message).to_vec();
+    transaction
+        .signatures
+        .resize(required.len(), Signature::default());
+    let message_data = transaction.message.serialize();
+    for (index, pubkey) in required.iter().enumerate() {
+        if let Some(signer) = signers.iter().find(|signer| signer.pubkey() == *pubkey) {
+            transaction.signatures[index] = signer
+                .try_sign_message(&message_data)
+                .map_err(|e| JitoError::Transaction(format!("{} failed to sign: {}", pubkey, e)))?;
+        }
+    }
+    Ok(())
+}
+
+/// transaction for `message` signed by the given signers, possibly partially
+pub fn sign_message(
+    message: VersionedMessage,
+    signers: &[&dyn Signer],
+) -> Result<VersionedTransaction, JitoError> {
+    let mut transaction = VersionedTransaction {
+        signatures: Vec::new(),
+        message,
+    };
+    partial_sign(&mut transaction, signers)?;
+    Ok(transaction)
+}
+
+/// Like `partial_sign`, with out-of-process signers; the required
+/// signatures are requested concurrently
+pub async fn partial_sign_async(
+    transaction: &mut VersionedTransaction,
+    signers: &[&dyn AsyncSigner],
+) -> Result<(), JitoError> {
+    let required = required_signers(&transaction.message).to_vec();
+    transaction
+        .signatures
+        .resize(required.len(), Signature::default());
+    let message_data = transaction.message.serialize();
+    let requests = required.iter().enumerate().filter_map(|(index, pubkey)| {
+        let signer = signers.iter().find(|signer| signer.pubkey() == *pubkey)?;
+        let message_data = &message_data;
+        Some(async move { (index, signer.sign(message_data).await) })
+    });
+    for (index, signature) in join_all(requests).await {
+        transaction.signatures[index] = signature?;
+    }
+    Ok(())
+}
@@ -0,0 +1,298 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use solana_rpc_client_api::config::{
+    RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig,
+};
+use solana_rpc_client_api::request::RpcRequest;
+use solana_sdk::transaction::VersionedTransaction;
+
+use crate::Jito;
+use crate::client::{JsonRpcTransport, RpcContextResponse};
+use crate::config::JitoConfig;
+use crate::encoding::{TxEncoding, encode_transactions};
+use crate::types::JitoError;
+
+/// Accounts to return before/after a transaction executes
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SimulationAccountsConfig {
+    pub addresses: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<String>,
+}
+
+impl SimulationAccountsConfig {
+    pub fn new(addresses: Vec<String>) -> Self {
+        Self {
+            addresses,
+            encoding: Some("base64".to_string()),
+        }
+    }
+}
+
+/// Options of `simulateBundle`. The account config lists are indexed by
+/// transaction; missing entries are sent as `null`.
+#[derive(Debug, Clone, Default)]
+pub struct SimulateBundleOptions {
+    pub pre_execution_accounts_configs: Vec<Option<SimulationAccountsConfig>>,
+    pub post_execution_accounts_configs: Vec<Option<SimulationAccountsConfig>>,
+    pub skip_sig_verify: bool,
+    pub replace_recent_blockhash: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SimulateBundleParams {
+    encoded_transactions: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SimulateBundleConfig {
+    pre_execution_accounts_configs: Vec<Option<SimulationAccountsConfig>>,
+    post_execution_accounts_configs: Vec<Option<SimulationAccountsConfig>>,
+    transaction_encoding: TxEncoding,
+    skip_sig_verify: bool,
+    replace_recent_blockhash: bool,
+}
+
+/// Overall bundle simulation result
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SimulationSummary {
+    Succeeded,
+    Failed {
+        error: serde_json::Value,
+        tx_signature: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationReturnData {
+    #[serde(rename = "programId")]
+    pub program_id: String,
+    /// `[data, encoding]`
+    pub data: (String, String),
+}
+
+/// Simulation result of a single transaction of the bundle
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionSimulation {
+    pub err: Option<serde_json::Value>,
+    pub logs: Option<Vec<String>>,
+    pub pre_execution_accounts: Option<Vec<serde_json::Value>>,
+    pub post_execution_accounts: Option<Vec<serde_json::Value>>,
+    pub units_consumed: Option<u64>,
+    pub return_data: Option<SimulationReturnData>,
+}
+
+/// Bundle simulation report, produced by the remote `simulateBundle` method
+/// or by the local sequential fallback
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulationReport {
+    pub summary: SimulationSummary,
+    pub transaction_results: Vec<TransactionSimulation>,
+    /// produced by `Jito::simulate_bundle_locally`: transactions were
+    /// simulated one by one without chaining state
+    #[serde(skip)]
+    pub sequential: bool,
+}
+
+impl SimulationReport {
+    pub fn is_success(&self) -> bool {
+        self.summary == SimulationSummary::Succeeded
+    }
+
+    /// index of the first failing transaction
+    pub fn failed_transaction_index(&self) -> Option<usize> {
+        if self.is_success() {
+            return None;
+        }
+        self.transaction_results
+            .iter()
+            .position(|result| result.err.is_some())
+            // results stop at the failing transaction
+            .or(Some(self.transaction_results.len()))
+    }
+
+    pub fn units_consumed(&self) -> u64 {
+        self.transaction_results
+            .iter()
+            .filter_map(|result| result.units_consumed)
+            .sum()
+    }
+}
+
+impl fmt::Display for SimulationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.summary {
+            SimulationSummary::Succeeded => write!(f, "succeeded")?,
+            SimulationSummary::Failed { error, .. } => match self.failed_transaction_index() {
+                Some(index) => write!(f, "transaction {} failed: {}", index, error)?,
+                None => write!(f, "failed: {}", error)?,
+            },
+        }
+        if self.sequential {
+            write!(f, " (sequential simulation)")?;
+        }
+        Ok(())
+    }
+}
+
+// ============== simulation client ==============
+
+/// Client of the `simulateBundle` method of a Jito-enabled Solana RPC
+#[derive(Debug, Clone)]
+pub struct SimulationClient {
+    transport: JsonRpcTransport,
+    url: Option<String>,
+}
+
+impl SimulationClient {
+    pub fn new(config: &JitoConfig, transport: JsonRpcTransport) -> Self {
+        Self {
+            transport,
+            url: config.simulation_rpc_url.clone(),
+        }
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.url.is_some()
+    }
+
+    /// `simulateBundle`
+    pub async fn simulate_bundle(
+        &self,
+        transactions: &[VersionedTransaction],
+        options: &SimulateBundleOptions,
+    ) -> Result<SimulationReport, JitoError> {
+        let url = self.url.as_deref().ok_or(JitoError::Config(
+            "no simulateBundle rpc configured".to_string(),
+        ))?;
+        let padded = |configs: &[Option<SimulationAccountsConfig>]| {
+            let mut configs = configs.to_vec();
+            configs.resize(transactions.len(), None);
+            configs
+        };
+        let params = (
+            SimulateBundleParams {
+                encoded_transactions: encode_transactions(transactions, TxEncoding::Base64)?,
+            },
+            SimulateBundleConfig {
+                pre_execution_accounts_configs: padded(&options.pre_execution_accounts_configs),
+                post_execution_accounts_configs: padded(&options.post_execution_accounts_configs),
+                transaction_encoding: TxEncoding::Base64,
+                skip_sig_verify: options.skip_sig_verify,
+                replace_recent_blockhash: options.replace_recent_blockhash,
+            },
+        );
+        let response: RpcContextResponse<SimulationReport> =
+            self.transport.call(url, "simulateBundle", params).await?;
+        Ok(response.value)
+    }
+}
+
+// ============== local fallback ==============
+
+fn to_json<T: Serialize>(value: T) -> serde_json::Value {
+    serde_json::to_value(value).unwrap_or_default()
+}
+
+impl Jito {
+    /// Approximate `simulateBundle` with one `simulateTransaction` per
+    /// transaction, in order, stopping at the first failure. The report is
+    /// marked `sequential` and is not equivalent to the remote simulator:
+    ///
+    /// - state does not chain: every transaction is simulated against the
+    ///   current bank, so effects of earlier bundle transactions are not
+    ///   applied and a transaction that depends on them may fail here
+    ///   although the bundle would land
+    /// - pre-execution accounts are the current bank state from
+    ///   `getMultipleAccounts`, not the state after the earlier transactions
+    pub async fn simulate_bundle_locally(
+        &self,
+        transactions: &[VersionedTransaction],
+        options: &SimulateBundleOptions,
+    ) -> Result<SimulationReport, JitoError> {
+        let config_addresses = |configs: &[Option<SimulationAccountsConfig>], index: usize| {
+            configs
+                .get(index)
+                .and_then(Option::as_ref)
+                .map(|config| config.addresses.clone())
+                .unwrap_or_default()
+        };
+        let client = self.solana.client_arc();
+        let mut transaction_results = Vec::with_capacity(transactions.len());
+        for (index, transaction) in transactions.iter().enumerate() {
+            let pre_addresses = config_addresses(&options.pre_execution_accounts_configs, index);
+            let post_addresses = config_addresses(&options.post_execution_accounts_configs, index);
+            let pre_execution_accounts = if pre_addresses.is_empty() {
+                None
+            } else {
+                Some(self.fetch_accounts(&pre_addresses).await?)
+            };
+            let config = RpcSimulateTransactionConfig {
+                // the rpc rejects signature verification with blockhash replacement
+                sig_verify: !options.skip_sig_verify && !options.replace_recent_blockhash,
+                replace_recent_blockhash: options.replace_recent_blockhash,
+                accounts: (!post_addresses.is_empty()).then_some(
+                    RpcSimulateTransactionAccountsConfig {
+                        encoding: None,
+                        addresses: post_addresses,
+                    },
+                ),
+                ..RpcSimulateTransactionConfig::default()
+            };
+            let result = client
+                .simulate_transaction_with_config(transaction, config)
+                .await?
+                .value;
+            let err = result.err.map(to_json);
+            transaction_results.push(TransactionSimulation {
+                err: err.clone(),
+                logs: result.logs,
+                pre_execution_accounts,
+                post_execution_accounts: result
+                    .accounts
+                    .map(|accounts| accounts.into_iter().map(to_json).collect()),
+                units_consumed: result.units_consumed,
+                return_data: result
+                    .return_data
+                    .and_then(|data| serde_json::from_value(to_json(data)).ok()),
+            });
+            if let Some(error) = err {
+                return Ok(SimulationReport {
+                    summary: SimulationSummary::Failed {
+                        error,
+                        tx_signature: transaction.signatures.first().map(|s| s.to_string()),
+                    },
+                    transaction_results,
+                    sequential: true,
+                });
+            }
+        }
+        Ok(SimulationReport {
+            summary: SimulationSummary::Succeeded,
+            transaction_results,
+            sequential: true,
+        })
+    }
+
+    /// current bank state of `addresses` in the `simulateTransaction`
+    /// account format, `null` for missing accounts
+    async fn fetch_accounts(
+        &self,
+        addresses: &[String],
+    ) -> Result<Vec<serde_json::Value>, JitoError> {
+        let client = self.solana.client_arc();
+        let params = serde_json::json!([
+            addresses,
+            { "encoding": "base64", "commitment": client.commitment().commitment },
+        ]);
+        let response: RpcContextResponse<Vec<serde_json::Value>> =
+            client.send(RpcRequest::GetMultipleAccounts, params).await?;
+        Ok(response.value)
+    }
+}
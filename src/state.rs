@@ -0,0 +1,253 @@
+use std::fmt;
+
+use crate::client::{BundleStatus, ConfirmationStatus, InflightBundleStatus, InflightStatus};
+use crate::types::JitoError;
+
+/// Bundle lifecycle
+///
+/// ```text
+/// Submitted -> Pending -> Landed -> Confirmed -> Finalized
+///     |           |
+///     +-----------+-----> Failed | Expired
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BundleState {
+    /// accepted by `sendBundle`, not yet seen by the block engine
+    Submitted,
+    /// in-flight, neither landed nor failed
+    Pending,
+    /// landed at `processed` commitment
+    Landed {
+        slot: u64,
+    },
+    Confirmed {
+        slot: u64,
+    },
+    Finalized {
+        slot: u64,
+    },
+    /// rejected by the block engine or landed with a transaction error
+    Failed {
+        reason: String,
+    },
+    /// dropped: the blockhash expired before the bundle landed
+    Expired,
+}
+
+impl BundleState {
+    /// no further transitions are possible
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            BundleState::Finalized { .. } | BundleState::Failed { .. } | BundleState::Expired
+        )
+    }
+
+    /// the bundle is on-chain (at any commitment)
+    pub fn is_landed(&self) -> bool {
+        self.slot().is_some()
+    }
+
+    pub fn slot(&self) -> Option<u64> {
+        match self {
+            BundleState::Landed { slot }
+            | BundleState::Confirmed { slot }
+            | BundleState::Finalized { slot } => Some(*slot),
+            _ => None,
+        }
+    }
+
+    /// commitment reached by a landed bundle
+    pub fn commitment(&self) -> Option<ConfirmationStatus> {
+        match self {
+            BundleState::Landed { .. } => Some(ConfirmationStatus::Processed),
+            BundleState::Confirmed { .. } => Some(ConfirmationStatus::Confirmed),
+            BundleState::Finalized { .. } => Some(ConfirmationStatus::Finalized),
+            _ => None,
+        }
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            BundleState::Submitted => 0,
+            BundleState::Pending => 1,
+            BundleState::Landed { .. } => 2,
+            BundleState::Confirmed { .. } => 3,
+            BundleState::Finalized { .. } | BundleState::Failed { .. } | BundleState::Expired => 4,
+        }
+    }
+
+    /// whether `next` is a valid successor of this state
+    pub fn can_transition_to(&self, next: &BundleState) -> bool {
+        if self == next {
+            return true;
+        }
+        if self.is_terminal() {
+            return false;
+        }
+        match next {
+            // a bundle that already landed can no longer fail or expire
+            BundleState::Failed { .. } | BundleState::Expired => !self.is_landed(),
+            _ => next.rank() > self.rank(),
+        }
+    }
+
+    /// move to `next`, rejecting invalid transitions
+    pub fn transition(self, next: BundleState) -> Result<BundleState, JitoError> {
+        if self.can_transition_to(&next) {
+            Ok(next)
+        } else {
+            Err(JitoError::Bundle(format!(
+                "invalid bundle state transition: {} -> {}",
+                self, next
+            )))
+        }
+    }
+
+    /// derive a state from the block engine status responses;
+    /// `None` when the bundle is unknown to the block engine
+    pub fn from_statuses(
+        inflight: Option<&InflightBundleStatus>,
+        status: Option<&BundleStatus>,
+    ) -> Option<BundleState> {
+        if let Some(status) = status {
+            if !status.is_ok() {
+                return Some(BundleState::Failed {
+                    reason: status.err.to_string(),
+                });
+            }
+            return Some(match status.confirmation_status {
+                ConfirmationStatus::Processed => BundleState::Landed { slot: status.slot },
+                ConfirmationStatus::Confirmed => BundleState::Confirmed { slot: status.slot },
+                ConfirmationStatus::Finalized => BundleState::Finalized { slot: status.slot },
+            });
+        }
+        let inflight = inflight?;
+        match inflight.status {
+            InflightStatus::Invalid => None,
+            InflightStatus::Pending => Some(BundleState::Pending),
+            InflightStatus::Failed => Some(BundleState::Failed {
+                reason: "bundle failed in all regions".to_string(),
+            }),
+            InflightStatus::Landed => Some(match inflight.landed_slot {
+                Some(slot) => BundleState::Landed { slot },
+                None => BundleState::Pending,
+            }),
+        }
+    }
+}
+
+impl fmt::Display for BundleState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BundleState::Submitted => write!(f, "submitted"),
+            BundleState::Pending => write!(f, "pending"),
+            BundleState::Landed { slot } => write!(f, "landed (slot {})", slot),
+            BundleState::Confirmed { slot } => write!(f, "confirmed (slot {})", slot),
+            BundleState::Finalized { slot } => write!(f, "finalized (slot {})", slot),
+            BundleState::Failed { reason } => write!(f, "failed: {}", reason),
+            BundleState::Expired => write!(f, "expired"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failed() -> BundleState {
+        BundleState::Failed {
+            reason: "rejected".to_string(),
+        }
+    }
+
+    #[test]
+    fn transition_table() {
+        use BundleState::*;
+        let cases = [
+            (Submitted, Pending, true),
+            (Submitted, Landed { slot: 1 }, true),
+            (Submitted, failed(), true),
+            (Submitted, Expired, true),
+            (Pending, Confirmed { slot: 1 }, true),
+            (Pending, failed(), true),
+            (Pending, Expired, true),
+            (Pending, Submitted, false),
+            (Landed { slot: 1 }, Confirmed { slot: 1 }, true),
+            (Landed { slot: 1 }, Finalized { slot: 1 }, true),
+            (Landed { slot: 1 }, Pending, false),
+            (Landed { slot: 1 }, failed(), false),
+            (Landed { slot: 1 }, Expired, false),
+            (Confirmed { slot: 1 }, Finalized { slot: 1 }, true),
+            (Confirmed { slot: 1 }, Landed { slot: 1 }, false),
+            (Confirmed { slot: 1 }, failed(), false),
+            (Confirmed { slot: 1 }, Expired, false),
+            (Finalized { slot: 1 }, Finalized { slot: 1 }, true),
+            (Finalized { slot: 1 }, failed(), false),
+            (failed(), Pending, false),
+            (failed(), Expired, false),
+            (Expired, Landed { slot: 1 }, false),
+            (Expired, failed(), false),
+        ];
+        for (from, to, allowed) in cases {
+            assert_eq!(from.can_transition_to(&to), allowed, "{} -> {}", from, to);
+            assert_eq!(from.transition(to).is_ok(), allowed);
+        }
+    }
+
+    #[test]
+    fn from_statuses() {
+        let inflight = |status, landed_slot| InflightBundleStatus {
+            bundle_id: "id".to_string(),
+            status,
+            landed_slot,
+        };
+        let status = |confirmation_status, err| BundleStatus {
+            bundle_id: "id".to_string(),
+            transactions: Vec::new(),
+            slot: 7,
+            confirmation_status,
+            err,
+        };
+        let ok = serde_json::json!({ "Ok": null });
+        assert_eq!(BundleState::from_statuses(None, None), None);
+        assert_eq!(
+            BundleState::from_statuses(Some(&inflight(InflightStatus::Invalid, None)), None),
+            None
+        );
+        assert_eq!(
+            BundleState::from_statuses(Some(&inflight(InflightStatus::Pending, None)), None),
+            Some(BundleState::Pending)
+        );
+        assert_eq!(
+            BundleState::from_statuses(Some(&inflight(InflightStatus::Landed, Some(7))), None),
+            Some(BundleState::Landed { slot: 7 })
+        );
+        assert!(matches!(
+            BundleState::from_statuses(Some(&inflight(InflightStatus::Failed, None)), None),
+            Some(BundleState::Failed { .. })
+        ));
+        // the final status takes precedence over the in-flight status
+        assert_eq!(
+            BundleState::from_statuses(
+                Some(&inflight(InflightStatus::Pending, None)),
+                Some(&status(ConfirmationStatus::Finalized, ok.clone())),
+            ),
+            Some(BundleState::Finalized { slot: 7 })
+        );
+        assert_eq!(
+            BundleState::from_statuses(None, Some(&status(ConfirmationStatus::Confirmed, ok))),
+            Some(BundleState::Confirmed { slot: 7 })
+        );
+        assert!(matches!(
+            BundleState::from_statuses(
+                None,
+                Some(&status(
+                    ConfirmationStatus::Processed,
+                    serde_json::json!({ "Err": "InstructionError" }),
+                )),
+            ),
+            Some(BundleState::Failed { .. })
+        ));
+    }
+}
@@ -0,0 +1,477 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use rand::seq::SliceRandom;
+use reqwest::Client;
+use serde::Deserialize;
+use solana_network_sdk::tool::token::safe_sol_to_lamports;
+use solana_sdk::{
+    hash::Hash, message::Instruction, message::Message, pubkey::Pubkey, signer::Signer,
+    transaction::Transaction,
+};
+use solana_system_interface::instruction as system_instruction;
+use tokio::sync::RwLock;
+
+use crate::client::get_json;
+use crate::global::{MAINNET_TIP_ACCOUNTS, TIP_FLOOR_URL};
+use crate::retry::RetryPolicy;
+use crate::types::JitoError;
+
+/// Where the tip transfer is placed inside the bundle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TipPlacement {
+    /// appended to the instructions of the final transaction
+    #[default]
+    LastTransaction,
+    /// a standalone transaction at the end of the bundle, signed with the
+    /// same blockhash as the rest of the bundle
+    SeparateTransaction,
+}
+
+/// How a bundle pays its Jito tip. The tip is a plain
+/// `system_instruction::transfer` inside the bundle, so it is only paid
+/// when the bundle lands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TipStrategy {
+    pub lamports: u64,
+    pub placement: TipPlacement,
+    /// fixed tip account, a random Jito tip account when `None`
+    pub tip_account: Option<Pubkey>,
+}
+
+impl TipStrategy {
+    /// tip `lamports` in the final transaction, random tip account
+    pub fn new(lamports: u64) -> Self {
+        Self {
+            lamports,
+            placement: TipPlacement::default(),
+            tip_account: None,
+        }
+    }
+
+    pub fn with_placement(mut self, placement: TipPlacement) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    pub fn with_tip_account(mut self, tip_account: Pubkey) -> Self {
+        self.tip_account = Some(tip_account);
+        self
+    }
+
+    /// tip transfer instruction
+    pub fn instruction(&self, payer: &Pubkey, tip_account: &Pubkey) -> Instruction {
+        system_instruction::transfer(payer, tip_account, self.lamports)
+    }
+
+    /// standalone tip transaction
+    pub fn build_transaction(
+        &self,
+        payer: &dyn Signer,
+        tip_account: &Pubkey,
+        recent_blockhash: Hash,
+    ) -> Result<Transaction, JitoError> {
+        let message = Message::new_with_blockhash(
+            &[self.instruction(&payer.pubkey(), tip_account)],
+            Some(&payer.pubkey()),
+            &recent_blockhash,
+        );
+        let mut transaction = Transaction::new_unsigned(message);
+        transaction
+            .try_sign(&[payer], recent_blockhash)
+            .map_err(|e| JitoError::Transaction(format!("failed to sign tip: {}", e)))?;
+        Ok(transaction)
+    }
+}
+
+// ============== tip floor oracle ==============
+
+/// Landed tip percentile of the tip floor feed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TipPercentile {
+    P25,
+    P50,
+    P75,
+    P95,
+    P99,
+    /// exponential moving average of the 50th percentile
+    Ema50,
+}
+
+/// One sample of the tip floor feed, amounts in SOL
+#[derive(Debug, Clone, Deserialize)]
+pub struct TipFloor {
+    pub time: String,
+    pub landed_tips_25th_percentile: f64,
+    pub landed_tips_50th_percentile: f64,
+    pub landed_tips_75th_percentile: f64,
+    pub landed_tips_95th_percentile: f64,
+    pub landed_tips_99th_percentile: f64,
+    pub ema_landed_tips_50th_percentile: f64,
+}
+
+impl TipFloor {
+    /// percentile in lamports
+    pub fn lamports(&self, percentile: TipPercentile) -> u64 {
+        let sol = match percentile {
+            TipPercentile::P25 => self.landed_tips_25th_percentile,
+            TipPercentile::P50 => self.landed_tips_50th_percentile,
+            TipPercentile::P75 => self.landed_tips_75th_percentile,
+            TipPercentile::P95 => self.landed_tips_95th_percentile,
+            TipPercentile::P99 => self.landed_tips_99th_percentile,
+            TipPercentile::Ema50 => self.ema_landed_tips_50th_percentile,
+        };
+        safe_sol_to_lamports(sol).unwrap_or(0)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TipOracleConfig {
+    /// tip floor feed url, can point at a local fixture server
+    pub url: String,
+    /// how long a fetched sample, or a failed fetch, is reused
+    pub ttl: Duration,
+    /// tip returned when the feed is unavailable
+    pub fallback_lamports: u64,
+}
+
+impl Default for TipOracleConfig {
+    fn default() -> Self {
+        Self {
+            url: TIP_FLOOR_URL.to_string(),
+            ttl: Duration::from_secs(10),
+            fallback_lamports: safe_sol_to_lamports(0.00001).unwrap_or(10_000), // 0.00001 SOL
+        }
+    }
+}
+
+/// Tip recommendations backed by the landed tip percentile feed
+#[derive(Debug)]
+pub struct TipOracle {
+    client: Client,
+    config: TipOracleConfig,
+    retry: RetryPolicy,
+    /// last fetch attempt and its sample, `None` when it failed
+    cache: RwLock<Option<(Instant, Option<TipFloor>)>>,
+}
+
+impl TipOracle {
+    pub fn new(config: TipOracleConfig) -> Self {
+        Self {
+            client: Client::new(),
+            config,
+            retry: RetryPolicy::default(),
+            cache: RwLock::new(None),
+        }
+    }
+
+    /// use a shared HTTP client
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// fetch the latest sample, bypassing the cache
+    pub async fn fetch_tip_floor(&self) -> Result<TipFloor, JitoError> {
+        let samples: Vec<TipFloor> = get_json(&self.client, &self.config.url, &self.retry).await?;
+        samples
+            .into_iter()
+            .next()
+            .ok_or(JitoError::Tip("empty tip floor response".to_string()))
+    }
+
+    /// Latest sample, served from the cache while younger than the ttl. A
+    /// failed fetch is cached too, so a stalled feed is only retried after
+    /// the ttl instead of on every call.
+    pub async fn tip_floor(&self) -> Result<TipFloor, JitoError> {
+        if let Some((fetched_at, floor)) = self.cache.read().await.as_ref()
+            && fetched_at.elapsed() < self.config.ttl
+        {
+            return floor.clone().ok_or(JitoError::Tip(
+                "tip floor unavailable, retrying after the ttl".to_string(),
+            ));
+        }
+        let result = self.fetch_tip_floor().await;
+        *self.cache.write().await = Some((Instant::now(), result.as_ref().ok().cloned()));
+        result
+    }
+
+    /// recommended tip in lamports, the static floor when the feed is unavailable
+    pub async fn recommended_tip(&self, percentile: TipPercentile) -> u64 {
+        match self.tip_floor().await {
+            Ok(floor) => floor.lamports(percentile),
+            Err(e) => {
+                log::warn!("Tip floor unavailable, using static floor: {}", e);
+                self.config.fallback_lamports
+            }
+        }
+    }
+}
+
+// ============== tip account selection ==============
+
+/// How a tip account is picked for each bundle
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum TipAccountPolicy {
+    /// uniformly random account
+    #[default]
+    Random,
+    /// cycle through the accounts in order
+    RoundRobin,
+    /// the account that was used the longest time ago (or never)
+    LeastRecentlyUsed,
+    /// always the same account, which must be a tip account
+    Pinned(Pubkey),
+}
+
+#[derive(Debug)]
+struct SelectorState {
+    accounts: Vec<Pubkey>,
+    refreshed_at: Option<Instant>,
+    /// last refresh attempt, successful or not
+    refresh_attempted_at: Option<Instant>,
+    next: usize,
+    last_used: HashMap<Pubkey, Instant>,
+}
+
+/// Spreads tips across the Jito tip accounts so bundles don't all contend
+/// for the write lock of a single account.
+#[derive(Debug)]
+pub struct TipAccountSelector {
+    policy: TipAccountPolicy,
+    state: Mutex<SelectorState>,
+}
+
+impl TipAccountSelector {
+    /// selector over the built-in mainnet tip accounts
+    pub fn new(policy: TipAccountPolicy) -> Self {
+        Self {
+            policy,
+            state: Mutex::new(SelectorState {
+                accounts: Self::fallback_accounts(),
+                refreshed_at: None,
+                refresh_attempted_at: None,
+                next: 0,
+                last_used: HashMap::new(),
+            }),
+        }
+    }
+
+    /// the eight known mainnet tip accounts
+    pub fn fallback_accounts() -> Vec<Pubkey> {
+        MAINNET_TIP_ACCOUNTS
+            .iter()
+            .filter_map(|pubkey| Pubkey::from_str(pubkey).ok())
+            .collect()
+    }
+
+    pub fn policy(&self) -> &TipAccountPolicy {
+        &self.policy
+    }
+
+    /// replace the account list (e.g. with the result of getTipAccounts);
+    /// an empty list keeps the current accounts
+    pub fn set_accounts(&self, accounts: Vec<Pubkey>) {
+        if accounts.is_empty() {
+            return;
+        }
+        let mut state = self.state.lock().unwrap();
+        state.accounts = accounts;
+        state.refreshed_at = Some(Instant::now());
+        state.next = 0;
+    }
+
+    pub fn accounts(&self) -> Vec<Pubkey> {
+        self.state.lock().unwrap().accounts.clone()
+    }
+
+    /// time since the accounts were last refreshed from the block engine
+    pub fn refreshed_elapsed(&self) -> Option<Duration> {
+        self.state
+            .lock()
+            .unwrap()
+            .refreshed_at
+            .map(|refreshed_at| refreshed_at.elapsed())
+    }
+
+    /// Claim a refresh when none was attempted within `interval`. The
+    /// attempt is recorded whether or not it succeeds, so a failing
+    /// getTipAccounts is only retried after another `interval`.
+    pub fn claim_refresh(&self, interval: Duration) -> bool {
+        let mut state = self.state.lock().unwrap();
+        let due = state
+            .refresh_attempted_at
+            .is_none_or(|attempted_at| attempted_at.elapsed() >= interval);
+        if due {
+            state.refresh_attempted_at = Some(Instant::now());
+        }
+        due
+    }
+
+    pub fn is_tip_account(&self, pubkey: &Pubkey) -> bool {
+        self.state.lock().unwrap().accounts.contains(pubkey)
+    }
+
+    /// reject tip destinations that are not Jito tip accounts
+    pub fn validate(&self, pubkey: &Pubkey) -> Result<(), JitoError> {
+        if self.is_tip_account(pubkey) {
+            Ok(())
+        } else {
+            Err(JitoError::Tip(format!(
+                "{} is not a Jito tip account",
+                pubkey
+            )))
+        }
+    }
+
+    /// pick the tip account for the next bundle
+    pub fn select(&self) -> Result<Pubkey, JitoError> {
+        if let TipAccountPolicy::Pinned(pubkey) = &self.policy {
+            self.validate(pubkey)?;
+            return Ok(*pubkey);
+        }
+        let mut state = self.state.lock().unwrap();
+        let selected = match self.policy {
+            TipAccountPolicy::Random => state.accounts.choose(&mut rand::thread_rng()).copied(),
+            TipAccountPolicy::RoundRobin => {
+                let index = state.next % state.accounts.len().max(1);
+                state.next = index + 1;
+                state.accounts.get(index).copied()
+            }
+            TipAccountPolicy::LeastRecentlyUsed => state
+                .accounts
+                .iter()
+                .min_by_key(|pubkey| state.last_used.get(*pubkey))
+                .copied(),
+            TipAccountPolicy::Pinned(_) => unreachable!(),
+        };
+        let selected = selected.ok_or(JitoError::Tip("no tip accounts".to_string()))?;
+        state.last_used.insert(selected, Instant::now());
+        Ok(selected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    const TIP_FLOOR_FIXTURE: &str = r#"[{
+        "time": "2024-09-01T12:58:00Z",
+        "landed_tips_25th_percentile": 0.000001,
+        "landed_tips_50th_percentile": 0.00001,
+        "landed_tips_75th_percentile": 0.00005,
+        "landed_tips_95th_percentile": 0.0001,
+        "landed_tips_99th_percentile": 0.001,
+        "ema_landed_tips_50th_percentile": 0.00002
+    }]"#;
+
+    /// serves the tip floor fixture on a local port; returns its url and
+    /// the number of requests served
+    async fn spawn_fixture_server() -> (String, Arc<AtomicUsize>) {
+        spawn_server("200 OK", TIP_FLOOR_FIXTURE).await
+    }
+
+    /// serves `body` with `status` on a local port; returns its url and the
+    /// number of requests served
+    async fn spawn_server(status: &'static str, body: &'static str) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/tip_floor", listener.local_addr().unwrap());
+        let requests = Arc::new(AtomicUsize::new(0));
+        let served = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = Vec::new();
+                let mut buf = [0; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    match stream.read(&mut buf).await {
+                        Ok(0) | Err(_) => break,
+                        Ok(read) => request.extend_from_slice(&buf[..read]),
+                    }
+                }
+                served.fetch_add(1, Ordering::SeqCst);
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+                let _ = stream.shutdown().await;
+            }
+        });
+        (url, requests)
+    }
+
+    fn oracle(url: String, ttl: Duration) -> TipOracle {
+        TipOracle::new(TipOracleConfig {
+            url,
+            ttl,
+            fallback_lamports: 12_345,
+        })
+        .with_retry_policy(RetryPolicy::none())
+    }
+
+    #[tokio::test]
+    async fn recommended_tip_per_percentile() {
+        let (url, _) = spawn_fixture_server().await;
+        let oracle = oracle(url, Duration::from_secs(60));
+        for (percentile, lamports) in [
+            (TipPercentile::P25, 1_000),
+            (TipPercentile::P50, 10_000),
+            (TipPercentile::P75, 50_000),
+            (TipPercentile::P95, 100_000),
+            (TipPercentile::P99, 1_000_000),
+            (TipPercentile::Ema50, 20_000),
+        ] {
+            assert_eq!(oracle.recommended_tip(percentile).await, lamports);
+        }
+    }
+
+    #[tokio::test]
+    async fn tip_floor_is_cached_for_the_ttl() {
+        let (url, requests) = spawn_fixture_server().await;
+        let oracle = oracle(url, Duration::from_millis(200));
+        oracle.recommended_tip(TipPercentile::P50).await;
+        oracle.recommended_tip(TipPercentile::P75).await;
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        oracle.recommended_tip(TipPercentile::P50).await;
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn falls_back_when_the_feed_is_down() {
+        // bind and drop a listener so nothing serves the port
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/tip_floor", listener.local_addr().unwrap());
+        drop(listener);
+        let oracle = oracle(url, Duration::from_secs(60));
+        assert!(oracle.tip_floor().await.is_err());
+        assert_eq!(oracle.recommended_tip(TipPercentile::P50).await, 12_345);
+    }
+
+    #[tokio::test]
+    async fn failed_fetch_is_cached_for_the_ttl() {
+        let (url, requests) = spawn_server("503 Service Unavailable", "{}").await;
+        let oracle = oracle(url, Duration::from_millis(200));
+        assert_eq!(oracle.recommended_tip(TipPercentile::P50).await, 12_345);
+        assert_eq!(oracle.recommended_tip(TipPercentile::P75).await, 12_345);
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        oracle.recommended_tip(TipPercentile::P50).await;
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+}
@@ -3,60 +3,77 @@ use std::sync::Arc;
 use crate::ArbitrageOpportunity;
 use crate::Jito;
 use crate::JitoError;
+use crate::bundle::{build_v0_transaction, with_compute_budget};
+use crate::tip::TipStrategy;
 use solana_network_sdk::tool::token;
-use solana_program::example_mocks::solana_sdk::system_instruction;
+use solana_sdk::hash::Hash;
+use solana_sdk::message::Instruction;
 use solana_sdk::signer::Signer;
-use solana_sdk::{message::Message, pubkey::Pubkey, signature::Keypair, transaction::Transaction};
+use solana_sdk::transaction::VersionedTransaction;
+use solana_sdk::{pubkey::Pubkey, transaction::Transaction};
 
 /// build arbitrage transactions
 pub async fn build_arbitrage_transactions(
     jito: Arc<Jito>,
-    wallet: &Keypair,
+    wallet: &dyn Signer,
     opportunity: &ArbitrageOpportunity,
     tip_account: Pubkey,
     tip_amount: u64,
-) -> Result<Vec<Transaction>, JitoError<String>> {
+    compute_unit_limit: Option<u32>,
+    compute_unit_price: Option<u64>,
+) -> Result<Vec<VersionedTransaction>, JitoError> {
     let mut transactions = Vec::new();
-    let arbitrage_tx = build_dex_swap_transaction(jito, wallet, opportunity).await?;
+    let arbitrage_tx = build_dex_swap_transaction(
+        jito,
+        wallet,
+        opportunity,
+        compute_unit_limit,
+        compute_unit_price,
+    )
+    .await?;
+    // sign the tip with the swap's blockhash so both share one lifetime
+    let recent_blockhash = *arbitrage_tx.message.recent_blockhash();
     transactions.push(arbitrage_tx);
-    let tip_tx = build_tip_transaction(wallet, tip_account, tip_amount).await?;
-    transactions.push(tip_tx);
+    let tip_tx = build_tip_transaction(wallet, tip_account, tip_amount, recent_blockhash).await?;
+    transactions.push(tip_tx.into());
     Ok(transactions)
 }
 
-/// build dex swap transaction
+/// build dex swap transaction (v0 message, resolving route accounts through
+/// the opportunity's address lookup tables) with the compute budget prepended
+#[allow(unreachable_code, unused_variables)]
 pub async fn build_dex_swap_transaction(
     jito: Arc<Jito>,
-    wallet: &Keypair,
+    wallet: &dyn Signer,
     opportunity: &ArbitrageOpportunity,
-) -> Result<Transaction, JitoError<String>> {
+    compute_unit_limit: Option<u32>,
+    compute_unit_price: Option<u64>,
+) -> Result<VersionedTransaction, JitoError> {
     todo!();
-    let recent_blockhash = jito
-        .solana
-        .client_arc()
-        .get_latest_blockhash()
-        .await
-        .map_err(|e| JitoError::Error(format!("{:?}", e)))?;
-    let message = Message::new_with_blockhash(
-        &[], // 实际的 swap instructions
-        Some(&wallet.pubkey()),
-        &recent_blockhash,
-    );
-    Ok(Transaction::new(&[wallet], message, recent_blockhash))
+    let recent_blockhash = jito.solana.client_arc().get_latest_blockhash().await?;
+    let swap_instructions: Vec<Instruction> = Vec::new(); // 实际的 swap instructions
+    let instructions =
+        with_compute_budget(swap_instructions, compute_unit_limit, compute_unit_price);
+    build_v0_transaction(
+        &[wallet],
+        &wallet.pubkey(),
+        &instructions,
+        &opportunity.lookup_tables,
+        recent_blockhash,
+    )
 }
 
 pub async fn build_tip_transaction(
-    wallet: &Keypair,
+    wallet: &dyn Signer,
     tip_account: Pubkey,
     tip_amount: u64,
-) -> Result<Transaction, JitoError<String>> {
-    let recent_blockhash = /* 从您的 Solana RPC 获取 */ solana_sdk::hash::Hash::default();
-    let tip_instruction = system_instruction::transfer(&wallet.pubkey(), &tip_account, tip_amount);
-    let message = Message::new(&[tip_instruction], Some(&wallet.pubkey()));
-    Ok(Transaction::new(&[wallet], message, recent_blockhash))
+    recent_blockhash: Hash,
+) -> Result<Transaction, JitoError> {
+    TipStrategy::new(tip_amount).build_transaction(wallet, &tip_account, recent_blockhash)
 }
 
 /// calculate optimal tip
+#[allow(dead_code)]
 fn cal_optimal_tip(expected_profit: u64, network_congestion: f64, tip_percentage: f64) -> u64 {
     // Basic tip = a percentage of expected profit
     let base_tip =
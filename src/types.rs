@@ -1,49 +1,306 @@
-use std::fmt::{self, Debug, Display};
-
-use serde::Deserialize;
-
-pub type JitoResult<T, E> = Result<T, JitoError<E>>;
-
-#[derive(Debug, Deserialize)]
-pub enum JitoError<T> {
-    BundleError(T),
-    TipError(T),
-    BlockEngineError(T),
-    ValidatorsError(T),
-    TransactionsPoolError(T),
-    HealthError(T),
-    StatisticsError(T),
-    SerializationError(T),
-    Error(T),
-    InsufficientBalance,
+use std::fmt;
+use std::time::Duration;
+
+use reqwest::StatusCode;
+use reqwest::header::RETRY_AFTER;
+use solana_rpc_client_api::client_error::{Error as ClientError, ErrorKind as ClientErrorKind};
+
+use crate::client::JsonRpcError;
+use crate::simulation::SimulationReport;
+use crate::validation::BundleViolation;
+
+pub type JitoResult<T> = Result<T, JitoError>;
+
+/// JSON-RPC error codes with a specific meaning
+pub mod rpc_code {
+    pub const INVALID_REQUEST: i64 = -32600;
+    pub const METHOD_NOT_FOUND: i64 = -32601;
+    pub const INVALID_PARAMS: i64 = -32602;
+    pub const INTERNAL_ERROR: i64 = -32603;
+    /// solana rpc node is behind or unhealthy
+    pub const NODE_UNHEALTHY: i64 = -32005;
+    /// block engine rate limit
+    pub const RATE_LIMITED: i64 = -32097;
+}
+
+#[derive(Debug)]
+pub enum JitoError {
+    /// the request could not be sent or its response could not be read
+    Transport(reqwest::Error),
+    /// non-2xx response without a JSON-RPC error body
+    HttpStatus {
+        status: u16,
+        body: String,
+    },
+    /// JSON-RPC error returned by the block engine or a Jito rpc
+    Rpc(JsonRpcError),
+    /// HTTP 429 / rate limit error; retry after the given delay when known
+    RateLimited {
+        retry_after: Option<Duration>,
+    },
+    /// rejected by the client-side rate limiter (fail-fast mode)
+    Throttled {
+        method: String,
+        wait: Duration,
+    },
+    /// the bundle breaks block engine rules, nothing was submitted
+    InvalidBundle(Vec<BundleViolation>),
+    /// the block engine refused the bundle
+    BundleRejected {
+        reason: String,
+    },
+    /// bundle simulation failed, nothing was submitted
+    SimulationFailed {
+        /// index of the failing transaction
+        transaction_index: Option<usize>,
+        report: Box<SimulationReport>,
+    },
+    /// the bundle's blockhash is no longer valid; rebuild it with a fresh one
+    BlockhashExpired,
+    InsufficientBalance {
+        required: u64,
+        available: u64,
+    },
+    /// solana rpc client error
+    Solana(Box<ClientError>),
+    /// the block engine reported itself unhealthy
+    Unhealthy(String),
+    /// encoding or decoding of transactions and accounts
+    Serialization(String),
+    /// a transaction could not be built or signed
+    Transaction(String),
+    /// unknown bundle or invalid bundle state transition
+    Bundle(String),
+    Tip(String),
+    Config(String),
     NoArbitrageOpportunity,
 }
 
-impl<T> fmt::Display for JitoError<T>
-where
-    T: Display,
-{
+impl JitoError {
+    /// error for a non-2xx response, reading the body and `Retry-After`
+    pub async fn from_response(response: reqwest::Response) -> Self {
+        let status = response.status();
+        let retry_after = retry_after(&response);
+        match response.text().await {
+            Ok(body) => Self::from_status(status, retry_after, body),
+            Err(e) => e.into(),
+        }
+    }
+
+    pub(crate) fn from_status(
+        status: StatusCode,
+        retry_after: Option<Duration>,
+        body: String,
+    ) -> Self {
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            return JitoError::RateLimited { retry_after };
+        }
+        JitoError::HttpStatus {
+            status: status.as_u16(),
+            body,
+        }
+    }
+
+    pub(crate) fn from_rpc(error: JsonRpcError, retry_after: Option<Duration>) -> Self {
+        if error.code == rpc_code::RATE_LIMITED {
+            return JitoError::RateLimited { retry_after };
+        }
+        JitoError::Rpc(error)
+    }
+
+    /// a failed simulation report; see `is_blockhash_expired` for failures
+    /// caused by an expired blockhash
+    pub fn simulation_failed(report: SimulationReport) -> Self {
+        JitoError::SimulationFailed {
+            transaction_index: report.failed_transaction_index(),
+            report: Box::new(report),
+        }
+    }
+
+    /// the bundle's blockhash expired, including simulations that failed
+    /// with `BlockhashNotFound`; rebuild the bundle with a fresh blockhash
+    pub fn is_blockhash_expired(&self) -> bool {
+        match self {
+            JitoError::BlockhashExpired => true,
+            JitoError::SimulationFailed { report, .. } => {
+                report.transaction_results.iter().any(|result| {
+                    result.err.as_ref().and_then(|err| err.as_str()) == Some("BlockhashNotFound")
+                })
+            }
+            _ => false,
+        }
+    }
+
+    /// HTTP status code, if the error came from a response
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            JitoError::Transport(e) => e.status().map(|status| status.as_u16()),
+            JitoError::HttpStatus { status, .. } => Some(*status),
+            JitoError::RateLimited { .. } => Some(StatusCode::TOO_MANY_REQUESTS.as_u16()),
+            _ => None,
+        }
+    }
+
+    /// JSON-RPC error code
+    pub fn rpc_code(&self) -> Option<i64> {
+        match self {
+            JitoError::Rpc(error) => Some(error.code),
+            _ => None,
+        }
+    }
+
+    /// delay requested by the server before retrying
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            JitoError::RateLimited { retry_after } => *retry_after,
+            _ => None,
+        }
+    }
+
+    /// whether sending the same request again may succeed
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            JitoError::Transport(e) => {
+                e.is_timeout() || e.is_connect() || e.is_request() || e.is_body()
+            }
+            JitoError::HttpStatus { status, .. } => {
+                *status >= 500 || *status == StatusCode::REQUEST_TIMEOUT.as_u16()
+            }
+            JitoError::Rpc(error) => matches!(
+                error.code,
+                rpc_code::INTERNAL_ERROR | rpc_code::NODE_UNHEALTHY
+            ),
+            JitoError::RateLimited { .. } | JitoError::Unhealthy(_) => true,
+            JitoError::Solana(e) => matches!(
+                e.kind(),
+                ClientErrorKind::Io(_) | ClientErrorKind::Reqwest(_)
+            ),
+            _ => false,
+        }
+    }
+}
+
+/// `Retry-After` in seconds
+pub(crate) fn retry_after(response: &reqwest::Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+impl fmt::Display for JitoError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            JitoError::BundleError(msg) => write!(f, "Bundle error: {}", msg),
-            JitoError::TipError(msg) => write!(f, "Tip error: {}", msg),
-            JitoError::BlockEngineError(msg) => write!(f, "Block engine error: {}", msg),
-            JitoError::ValidatorsError(msg) => write!(f, "Validators error: {}", msg),
-            JitoError::TransactionsPoolError(msg) => write!(f, "Transactions pool error: {}", msg),
-            JitoError::HealthError(msg) => write!(f, "Health error: {}", msg),
-            JitoError::StatisticsError(msg) => write!(f, "Statistics error: {}", msg),
-            JitoError::SerializationError(msg) => write!(f, "Serialization error: {}", msg),
-            JitoError::InsufficientBalance => write!(f, "Insufficient balance"),
+            JitoError::Transport(e) => write!(f, "Transport error: {}", e),
+            JitoError::HttpStatus { status, body } => write!(f, "HTTP {}: {}", status, body),
+            JitoError::Rpc(error) => write!(f, "RPC error: {}", error),
+            JitoError::RateLimited {
+                retry_after: Some(retry_after),
+            } => write!(f, "Rate limited, retry after {:?}", retry_after),
+            JitoError::RateLimited { retry_after: None } => write!(f, "Rate limited"),
+            JitoError::Throttled { method, wait } => {
+                write!(
+                    f,
+                    "{} throttled by client rate limit for {:?}",
+                    method, wait
+                )
+            }
+            JitoError::InvalidBundle(violations) => {
+                let violations: Vec<String> = violations.iter().map(ToString::to_string).collect();
+                write!(f, "Invalid bundle: {}", violations.join("; "))
+            }
+            JitoError::BundleRejected { reason } => write!(f, "Bundle rejected: {}", reason),
+            JitoError::SimulationFailed { report, .. } => {
+                write!(f, "Bundle simulation {}", report)
+            }
+            JitoError::BlockhashExpired => write!(f, "Blockhash expired"),
+            JitoError::InsufficientBalance {
+                required,
+                available,
+            } => write!(
+                f,
+                "Insufficient balance: required {} lamports, available {}",
+                required, available
+            ),
+            JitoError::Solana(e) => write!(f, "Solana rpc error: {}", e),
+            JitoError::Unhealthy(status) => write!(f, "Block engine unhealthy: {}", status),
+            JitoError::Serialization(msg) => write!(f, "Serialization error: {}", msg),
+            JitoError::Transaction(msg) => write!(f, "Transaction error: {}", msg),
+            JitoError::Bundle(msg) => write!(f, "Bundle error: {}", msg),
+            JitoError::Tip(msg) => write!(f, "Tip error: {}", msg),
+            JitoError::Config(msg) => write!(f, "Config error: {}", msg),
             JitoError::NoArbitrageOpportunity => write!(f, "No arbitrage opportunity found"),
-            JitoError::Error(msg) => write!(f, "Serialization error: {}", msg),
         }
     }
 }
 
-impl<T> std::error::Error for JitoError<T> where T: Display + Debug {}
+impl std::error::Error for JitoError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            JitoError::Transport(e) => Some(e),
+            JitoError::Rpc(error) => Some(error),
+            JitoError::Solana(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for JitoError {
+    /// the url is stripped, as it may carry the auth uuid as a query param
+    fn from(e: reqwest::Error) -> Self {
+        JitoError::Transport(e.without_url())
+    }
+}
+
+impl From<ClientError> for JitoError {
+    fn from(e: ClientError) -> Self {
+        JitoError::Solana(Box::new(e))
+    }
+}
+
+impl From<serde_json::Error> for JitoError {
+    fn from(e: serde_json::Error) -> Self {
+        JitoError::Serialization(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::{SimulationSummary, TransactionSimulation};
+
+    fn failed_report(err: &str) -> SimulationReport {
+        SimulationReport {
+            summary: SimulationSummary::Failed {
+                error: serde_json::json!(err),
+                tx_signature: None,
+            },
+            transaction_results: vec![TransactionSimulation {
+                err: Some(serde_json::json!(err)),
+                ..TransactionSimulation::default()
+            }],
+            sequential: false,
+        }
+    }
 
-impl<T> From<T> for JitoError<T> {
-    fn from(e: T) -> Self {
-        JitoError::SerializationError(e)
+    #[test]
+    fn expired_blockhash_keeps_the_report() {
+        let error = JitoError::simulation_failed(failed_report("BlockhashNotFound"));
+        assert!(error.is_blockhash_expired());
+        assert!(matches!(
+            error,
+            JitoError::SimulationFailed {
+                transaction_index: Some(0),
+                ..
+            }
+        ));
+        let error = JitoError::simulation_failed(failed_report("AccountNotFound"));
+        assert!(!error.is_blockhash_expired());
+        assert!(JitoError::BlockhashExpired.is_blockhash_expired());
     }
 }
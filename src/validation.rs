@@ -0,0 +1,601 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use solana_sdk::hash::Hash;
+use solana_sdk::message::VersionedMessage;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::VersionedTransaction;
+use solana_system_interface::program as system_program;
+
+use crate::Jito;
+use crate::encoding::serialize_transaction;
+use crate::global::{MAX_BUNDLE_TRANSACTIONS, MAX_TRANSACTION_SIZE};
+use crate::signing::missing_signers;
+use crate::tip::TipAccountSelector;
+use crate::types::JitoError;
+
+/// `SystemInstruction::Transfer` discriminant
+const SYSTEM_TRANSFER_TAG: [u8; 4] = 2u32.to_le_bytes();
+
+/// A rule broken by a bundle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BundleViolation {
+    Empty,
+    TooManyTransactions {
+        count: usize,
+        max: usize,
+    },
+    /// serialized transaction exceeds the packet size
+    TransactionTooLarge {
+        index: usize,
+        size: usize,
+        max: usize,
+    },
+    /// the transaction does not sanitize or serialize
+    Malformed {
+        index: usize,
+        reason: String,
+    },
+    /// the transaction requires no signatures, so it was never signed
+    Unsigned {
+        index: usize,
+    },
+    /// required signers that have not signed yet
+    MissingSignatures {
+        index: usize,
+        signers: Vec<Pubkey>,
+    },
+    InvalidSignature {
+        index: usize,
+        signer: Pubkey,
+    },
+    /// the transaction appears twice in the bundle
+    DuplicateSignature {
+        index: usize,
+        first_index: usize,
+    },
+    /// the transaction uses a different blockhash than the first one
+    MixedBlockhash {
+        index: usize,
+        blockhash: Hash,
+        expected: Hash,
+    },
+    /// the blockhash is no longer valid on the rpc
+    StaleBlockhash {
+        blockhash: Hash,
+    },
+    /// no transaction transfers lamports to a Jito tip account
+    MissingTip,
+}
+
+impl fmt::Display for BundleViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BundleViolation::Empty => write!(f, "bundle has no transactions"),
+            BundleViolation::TooManyTransactions { count, max } => {
+                write!(f, "bundle has {} transactions, at most {}", count, max)
+            }
+            BundleViolation::TransactionTooLarge { index, size, max } => write!(
+                f,
+                "transaction {} is {} bytes, at most {}",
+                index, size, max
+            ),
+            BundleViolation::Malformed { index, reason } => {
+                write!(f, "transaction {} is malformed: {}", index, reason)
+            }
+            BundleViolation::Unsigned { index } => write!(f, "transaction {} is unsigned", index),
+            BundleViolation::MissingSignatures { index, signers } => {
+                let signers: Vec<String> = signers.iter().map(ToString::to_string).collect();
+                write!(
+                    f,
+                    "transaction {} is missing signatures of {}",
+                    index,
+                    signers.join(", ")
+                )
+            }
+            BundleViolation::InvalidSignature { index, signer } => write!(
+                f,
+                "transaction {} has an invalid signature for {}",
+                index, signer
+            ),
+            BundleViolation::DuplicateSignature { index, first_index } => write!(
+                f,
+                "transaction {} duplicates transaction {}",
+                index, first_index
+            ),
+            BundleViolation::MixedBlockhash {
+                index,
+                blockhash,
+                expected,
+            } => write!(
+                f,
+                "transaction {} uses blockhash {}, expected {}",
+                index, blockhash, expected
+            ),
+            BundleViolation::StaleBlockhash { blockhash } => {
+                write!(f, "blockhash {} is no longer valid", blockhash)
+            }
+            BundleViolation::MissingTip => write!(f, "bundle has no tip to a Jito tip account"),
+        }
+    }
+}
+
+/// Checks a bundle against the block engine rules before it is submitted
+#[derive(Debug, Clone)]
+pub struct BundleValidator {
+    pub max_transactions: usize,
+    /// serialized size limit of each transaction
+    pub max_transaction_size: usize,
+    pub verify_signatures: bool,
+    /// every transaction must use the blockhash of the first one; disable for
+    /// bundles that include someone else's transaction, e.g. backruns
+    pub require_single_blockhash: bool,
+    /// check the blockhashes against the rpc (`Jito::validate_bundle` only)
+    pub check_blockhash_validity: bool,
+    pub require_tip: bool,
+    /// accepted tip destinations
+    pub tip_accounts: Vec<Pubkey>,
+}
+
+impl Default for BundleValidator {
+    fn default() -> Self {
+        Self {
+            max_transactions: MAX_BUNDLE_TRANSACTIONS,
+            max_transaction_size: MAX_TRANSACTION_SIZE,
+            verify_signatures: true,
+            require_single_blockhash: true,
+            check_blockhash_validity: true,
+            require_tip: true,
+            tip_accounts: TipAccountSelector::fallback_accounts(),
+        }
+    }
+}
+
+impl BundleValidator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_verify_signatures(mut self, verify_signatures: bool) -> Self {
+        self.verify_signatures = verify_signatures;
+        self
+    }
+
+    pub fn with_require_single_blockhash(mut self, require_single_blockhash: bool) -> Self {
+        self.require_single_blockhash = require_single_blockhash;
+        self
+    }
+
+    pub fn with_check_blockhash_validity(mut self, check_blockhash_validity: bool) -> Self {
+        self.check_blockhash_validity = check_blockhash_validity;
+        self
+    }
+
+    pub fn with_require_tip(mut self, require_tip: bool) -> Self {
+        self.require_tip = require_tip;
+        self
+    }
+
+    pub fn with_tip_accounts(mut self, tip_accounts: Vec<Pubkey>) -> Self {
+        self.tip_accounts = tip_accounts;
+        self
+    }
+
+    /// offline checks; empty when the bundle is valid
+    pub fn validate(&self, transactions: &[VersionedTransaction]) -> Vec<BundleViolation> {
+        let mut violations = Vec::new();
+        if transactions.is_empty() {
+            violations.push(BundleViolation::Empty);
+            return violations;
+        }
+        if transactions.len() > self.max_transactions {
+            violations.push(BundleViolation::TooManyTransactions {
+                count: transactions.len(),
+                max: self.max_transactions,
+            });
+        }
+        let expected_blockhash = *transactions[0].message.recent_blockhash();
+        let mut seen: HashMap<Signature, usize> = HashMap::new();
+        for (index, transaction) in transactions.iter().enumerate() {
+            self.check_transaction(index, transaction, &mut violations);
+            // unsigned slots are reported by the signature check
+            if let Some(signature) = transaction.signatures.first()
+                && *signature != Signature::default()
+            {
+                match seen.get(signature) {
+                    Some(&first_index) => {
+                        violations.push(BundleViolation::DuplicateSignature { index, first_index })
+                    }
+                    None => {
+                        seen.insert(*signature, index);
+                    }
+                }
+            }
+            let blockhash = *transaction.message.recent_blockhash();
+            if self.require_single_blockhash && blockhash != expected_blockhash {
+                violations.push(BundleViolation::MixedBlockhash {
+                    index,
+                    blockhash,
+                    expected: expected_blockhash,
+                });
+            }
+        }
+        if self.require_tip && !transactions.iter().any(|tx| self.has_tip(tx)) {
+            violations.push(BundleViolation::MissingTip);
+        }
+        violations
+    }
+
+    fn check_transaction(
+        &self,
+        index: usize,
+        transaction: &VersionedTransaction,
+        violations: &mut Vec<BundleViolation>,
+    ) {
+        match serialize_transaction(transaction) {
+            Ok(bytes) if bytes.len() > self.max_transaction_size => {
+                violations.push(BundleViolation::TransactionTooLarge {
+                    index,
+                    size: bytes.len(),
+                    max: self.max_transaction_size,
+                })
+            }
+            Ok(_) => {}
+            Err(e) => violations.push(BundleViolation::Malformed {
+                index,
+                reason: e.to_string(),
+            }),
+        }
+        if transaction.message.header().num_required_signatures == 0 {
+            violations.push(BundleViolation::Unsigned { index });
+            return;
+        }
+        if let Err(e) = transaction.sanitize() {
+            violations.push(BundleViolation::Malformed {
+                index,
+                reason: e.to_string(),
+            });
+            return;
+        }
+        let missing = missing_signers(transaction);
+        if !missing.is_empty() {
+            violations.push(BundleViolation::MissingSignatures {
+                index,
+                signers: missing,
+            });
+        }
+        if self.verify_signatures {
+            let signers = transaction.message.static_account_keys();
+            let results = transaction.verify_with_results();
+            for ((verified, signer), signature) in results
+                .into_iter()
+                .zip(signers)
+                .zip(&transaction.signatures)
+            {
+                // empty signatures are reported as missing
+                if !verified && *signature != Signature::default() {
+                    violations.push(BundleViolation::InvalidSignature {
+                        index,
+                        signer: *signer,
+                    });
+                }
+            }
+        }
+    }
+
+    /// whether the transaction transfers lamports to a tip account. Tip
+    /// accounts may not come from a lookup table, so only static keys count.
+    pub fn has_tip(&self, transaction: &VersionedTransaction) -> bool {
+        let keys = transaction.message.static_account_keys();
+        transaction
+            .message
+            .instructions()
+            .iter()
+            .any(|instruction| {
+                let is_transfer = keys.get(instruction.program_id_index as usize)
+                    == Some(&system_program::ID)
+                    && instruction.data.len() == 12
+                    && instruction.data[..4] == SYSTEM_TRANSFER_TAG;
+                is_transfer
+                    && instruction
+                        .accounts
+                        .get(1)
+                        .and_then(|&account| keys.get(account as usize))
+                        .is_some_and(|destination| self.tip_accounts.contains(destination))
+            })
+    }
+}
+
+impl Jito {
+    /// `JitoConfig::validation` with the current Jito tip accounts
+    pub async fn bundle_validator(&self) -> BundleValidator {
+        self.refresh_tip_accounts_if_stale().await;
+        self.config
+            .validation
+            .clone()
+            .with_tip_accounts(self.tip_selector.accounts())
+    }
+
+    /// validate a bundle with `Jito::bundle_validator`
+    pub async fn validate_bundle(
+        &self,
+        transactions: &[VersionedTransaction],
+    ) -> Result<(), JitoError> {
+        let validator = self.bundle_validator().await;
+        self.validate_bundle_with(&validator, transactions).await
+    }
+
+    /// Check that `payer` holds `tip_lamports` plus the fees (including
+    /// priority fees) of the transactions it pays for;
+    /// `JitoError::InsufficientBalance` otherwise
+    pub async fn check_payer_balance(
+        &self,
+        payer: &Pubkey,
+        transactions: &[VersionedTransaction],
+        tip_lamports: u64,
+    ) -> Result<(), JitoError> {
+        let client = self.solana.client_arc();
+        let mut required = tip_lamports;
+        for transaction in transactions {
+            if transaction.message.static_account_keys().first() != Some(payer) {
+                continue;
+            }
+            let fee = match &transaction.message {
+                VersionedMessage::Legacy(message) => client.get_fee_for_message(message).await?,
+                VersionedMessage::V0(message) => client.get_fee_for_message(message).await?,
+            };
+            required = required.saturating_add(fee);
+        }
+        let available = client.get_balance(payer).await?;
+        if available < required {
+            return Err(JitoError::InsufficientBalance {
+                required,
+                available,
+            });
+        }
+        Ok(())
+    }
+
+    /// offline checks plus, when enabled, blockhash validity on the rpc;
+    /// `JitoError::InvalidBundle` lists every violation
+    pub async fn validate_bundle_with(
+        &self,
+        validator: &BundleValidator,
+        transactions: &[VersionedTransaction],
+    ) -> Result<(), JitoError> {
+        let mut violations = validator.validate(transactions);
+        if validator.check_blockhash_validity {
+            let client = self.solana.client_arc();
+            let blockhashes: HashSet<Hash> = transactions
+                .iter()
+                .map(|transaction| *transaction.message.recent_blockhash())
+                .collect();
+            for blockhash in blockhashes {
+                if !client
+                    .is_blockhash_valid(&blockhash, client.commitment())
+                    .await?
+                {
+                    violations.push(BundleViolation::StaleBlockhash { blockhash });
+                }
+            }
+        }
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(JitoError::InvalidBundle(violations))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::instruction::AccountMeta;
+    use solana_sdk::message::{Instruction, Message};
+    use solana_sdk::signature::Keypair;
+    use solana_sdk::signer::Signer;
+    use solana_sdk::transaction::Transaction;
+    use solana_system_interface::instruction as system_instruction;
+
+    use super::*;
+
+    fn transfer(
+        payer: &Keypair,
+        to: &Pubkey,
+        lamports: u64,
+        blockhash: Hash,
+    ) -> VersionedTransaction {
+        let instruction = system_instruction::transfer(&payer.pubkey(), to, lamports);
+        Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[payer],
+            blockhash,
+        )
+        .into()
+    }
+
+    fn tip(payer: &Keypair, blockhash: Hash) -> VersionedTransaction {
+        transfer(
+            payer,
+            &TipAccountSelector::fallback_accounts()[0],
+            1_000,
+            blockhash,
+        )
+    }
+
+    #[test]
+    fn valid_bundle_has_no_violations() {
+        let payer = Keypair::new();
+        let blockhash = Hash::new_unique();
+        let bundle = vec![
+            transfer(&payer, &Pubkey::new_unique(), 1, blockhash),
+            tip(&payer, blockhash),
+        ];
+        assert_eq!(BundleValidator::new().validate(&bundle), vec![]);
+    }
+
+    #[test]
+    fn empty_bundle() {
+        assert_eq!(
+            BundleValidator::new().validate(&[]),
+            vec![BundleViolation::Empty]
+        );
+    }
+
+    #[test]
+    fn too_many_transactions() {
+        let payer = Keypair::new();
+        let blockhash = Hash::new_unique();
+        let mut bundle: Vec<_> = (1..=MAX_BUNDLE_TRANSACTIONS as u64)
+            .map(|lamports| transfer(&payer, &Pubkey::new_unique(), lamports, blockhash))
+            .collect();
+        bundle.push(tip(&payer, blockhash));
+        assert_eq!(
+            BundleValidator::new().validate(&bundle),
+            vec![BundleViolation::TooManyTransactions {
+                count: MAX_BUNDLE_TRANSACTIONS + 1,
+                max: MAX_BUNDLE_TRANSACTIONS,
+            }]
+        );
+    }
+
+    #[test]
+    fn oversized_transaction() {
+        let payer = Keypair::new();
+        let blockhash = Hash::new_unique();
+        let instruction = Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[0; MAX_TRANSACTION_SIZE],
+            vec![AccountMeta::new(payer.pubkey(), true)],
+        );
+        let large: VersionedTransaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[&payer],
+            blockhash,
+        )
+        .into();
+        let size = serialize_transaction(&large).unwrap().len();
+        assert_eq!(
+            BundleValidator::new().validate(&[tip(&payer, blockhash), large]),
+            vec![BundleViolation::TransactionTooLarge {
+                index: 1,
+                size,
+                max: MAX_TRANSACTION_SIZE,
+            }]
+        );
+    }
+
+    #[test]
+    fn unsigned_transaction() {
+        let payer = Keypair::new();
+        let blockhash = Hash::new_unique();
+        let instruction = Instruction::new_with_bytes(Pubkey::new_unique(), &[1], vec![]);
+        let mut message = Message::new(&[instruction], None);
+        message.recent_blockhash = blockhash;
+        let unsigned = Transaction::new_unsigned(message).into();
+        assert_eq!(
+            BundleValidator::new().validate(&[tip(&payer, blockhash), unsigned]),
+            vec![BundleViolation::Unsigned { index: 1 }]
+        );
+    }
+
+    #[test]
+    fn missing_signature() {
+        let payer = Keypair::new();
+        let blockhash = Hash::new_unique();
+        let mut unsigned = tip(&payer, blockhash);
+        unsigned.signatures[0] = Signature::default();
+        assert_eq!(
+            BundleValidator::new().validate(&[unsigned]),
+            vec![BundleViolation::MissingSignatures {
+                index: 0,
+                signers: vec![payer.pubkey()],
+            }]
+        );
+    }
+
+    #[test]
+    fn invalid_signature() {
+        let payer = Keypair::new();
+        let blockhash = Hash::new_unique();
+        let mut forged = tip(&payer, blockhash);
+        forged.signatures[0] = Signature::from([7; 64]);
+        assert_eq!(
+            BundleValidator::new().validate(&[forged]),
+            vec![BundleViolation::InvalidSignature {
+                index: 0,
+                signer: payer.pubkey(),
+            }]
+        );
+        // the check can be turned off, e.g. for bundles signed elsewhere
+        let mut forged = tip(&payer, blockhash);
+        forged.signatures[0] = Signature::from([7; 64]);
+        assert_eq!(
+            BundleValidator::new()
+                .with_verify_signatures(false)
+                .validate(&[forged]),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn duplicate_transaction() {
+        let payer = Keypair::new();
+        let tip = tip(&payer, Hash::new_unique());
+        assert_eq!(
+            BundleValidator::new().validate(&[tip.clone(), tip]),
+            vec![BundleViolation::DuplicateSignature {
+                index: 1,
+                first_index: 0,
+            }]
+        );
+    }
+
+    #[test]
+    fn mixed_blockhash() {
+        let payer = Keypair::new();
+        let expected = Hash::new_unique();
+        let blockhash = Hash::new_unique();
+        let bundle = vec![
+            tip(&payer, expected),
+            transfer(&payer, &Pubkey::new_unique(), 1, blockhash),
+        ];
+        assert_eq!(
+            BundleValidator::new().validate(&bundle),
+            vec![BundleViolation::MixedBlockhash {
+                index: 1,
+                blockhash,
+                expected,
+            }]
+        );
+        assert_eq!(
+            BundleValidator::new()
+                .with_require_single_blockhash(false)
+                .validate(&bundle),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn missing_tip() {
+        let payer = Keypair::new();
+        let bundle = vec![transfer(
+            &payer,
+            &Pubkey::new_unique(),
+            1_000,
+            Hash::new_unique(),
+        )];
+        assert_eq!(
+            BundleValidator::new().validate(&bundle),
+            vec![BundleViolation::MissingTip]
+        );
+        assert_eq!(
+            BundleValidator::new()
+                .with_require_tip(false)
+                .validate(&bundle),
+            vec![]
+        );
+    }
+}
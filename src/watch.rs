@@ -0,0 +1,200 @@
+use std::time::{Duration, Instant};
+
+use futures::{Stream, StreamExt};
+use tokio::time::sleep;
+
+use crate::Jito;
+use crate::client::ConfirmationStatus;
+use crate::state::BundleState;
+
+/// Options for `Jito::watch_bundle`
+#[derive(Debug, Clone)]
+pub struct WatchOptions {
+    /// commitment at which a landed bundle is reported as done
+    pub commitment: ConfirmationStatus,
+    /// last valid block height of the bundle's blockhash; once the chain
+    /// passes it an unlanded bundle is reported as expired
+    pub last_valid_block_height: Option<u64>,
+    /// wall-clock limit, mainly for bundles without a known blockhash deadline
+    pub timeout: Option<Duration>,
+    pub poll_interval: Duration,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        Self {
+            commitment: ConfirmationStatus::Confirmed,
+            last_valid_block_height: None,
+            timeout: Some(Duration::from_secs(60)),
+            poll_interval: Duration::from_secs(1),
+        }
+    }
+}
+
+impl WatchOptions {
+    pub fn new(commitment: ConfirmationStatus) -> Self {
+        Self {
+            commitment,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_last_valid_block_height(mut self, last_valid_block_height: u64) -> Self {
+        self.last_valid_block_height = Some(last_valid_block_height);
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+}
+
+/// Terminal result of watching a bundle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BundleOutcome {
+    /// landed and reached the requested commitment
+    Landed {
+        slot: u64,
+        commitment: ConfirmationStatus,
+    },
+    Failed {
+        reason: String,
+    },
+    /// blockhash expired before the bundle landed
+    Expired,
+    /// the watch timeout elapsed first; the bundle may still land
+    TimedOut {
+        last_state: BundleState,
+    },
+}
+
+/// Item of the `watch_bundle` stream
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WatchEvent {
+    /// the bundle moved to a new state
+    State(BundleState),
+    /// last item of the stream
+    Finished(BundleOutcome),
+}
+
+struct Watcher {
+    bundle_id: String,
+    options: WatchOptions,
+    state: BundleState,
+    started: Instant,
+    polled: bool,
+    outcome: Option<BundleOutcome>,
+    finished: bool,
+}
+
+impl Watcher {
+    /// outcome implied by the current state, if any
+    fn outcome(&self) -> Option<BundleOutcome> {
+        match &self.state {
+            BundleState::Failed { reason } => Some(BundleOutcome::Failed {
+                reason: reason.clone(),
+            }),
+            BundleState::Expired => Some(BundleOutcome::Expired),
+            state => match (state.slot(), state.commitment()) {
+                (Some(slot), Some(commitment)) if commitment >= self.options.commitment => {
+                    Some(BundleOutcome::Landed { slot, commitment })
+                }
+                _ => None,
+            },
+        }
+    }
+
+    async fn next_event(&mut self, jito: &Jito) -> Option<WatchEvent> {
+        if self.finished {
+            return None;
+        }
+        if let Some(outcome) = self.outcome.take() {
+            self.finished = true;
+            return Some(WatchEvent::Finished(outcome));
+        }
+        loop {
+            if self.polled {
+                sleep(self.options.poll_interval).await;
+            }
+            self.polled = true;
+            match jito.get_bundle_state(&self.bundle_id).await {
+                Ok(Some(next)) if next != self.state && self.state.can_transition_to(&next) => {
+                    self.state = next.clone();
+                    self.outcome = self.outcome();
+                    return Some(WatchEvent::State(next));
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    log::warn!("Failed to get bundle status: {}", e);
+                }
+            }
+            if let Some(last_valid_block_height) = self.options.last_valid_block_height
+                && !self.state.is_landed()
+            {
+                match jito.solana.client_arc().get_block_height().await {
+                    Ok(block_height) if block_height > last_valid_block_height => {
+                        self.state = BundleState::Expired;
+                        self.outcome = Some(BundleOutcome::Expired);
+                        return Some(WatchEvent::State(BundleState::Expired));
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        log::warn!("Failed to get block height: {}", e);
+                    }
+                }
+            }
+            if let Some(timeout) = self.options.timeout
+                && self.started.elapsed() >= timeout
+            {
+                self.finished = true;
+                return Some(WatchEvent::Finished(BundleOutcome::TimedOut {
+                    last_state: self.state.clone(),
+                }));
+            }
+        }
+    }
+}
+
+impl Jito {
+    /// Stream the state transitions of a submitted bundle. The stream ends
+    /// with a single `WatchEvent::Finished` carrying the terminal outcome.
+    pub fn watch_bundle<'a>(
+        &'a self,
+        bundle_id: &str,
+        options: WatchOptions,
+    ) -> impl Stream<Item = WatchEvent> + 'a {
+        let watcher = Watcher {
+            bundle_id: bundle_id.to_string(),
+            options,
+            state: BundleState::Submitted,
+            started: Instant::now(),
+            polled: false,
+            outcome: None,
+            finished: false,
+        };
+        futures::stream::unfold(watcher, move |mut watcher| async move {
+            watcher.next_event(self).await.map(|event| (event, watcher))
+        })
+    }
+
+    /// Drive `watch_bundle` to completion and return the terminal outcome.
+    pub async fn wait_for_bundle(&self, bundle_id: &str, options: WatchOptions) -> BundleOutcome {
+        let stream = self.watch_bundle(bundle_id, options);
+        futures::pin_mut!(stream);
+        while let Some(event) = stream.next().await {
+            match event {
+                WatchEvent::State(state) => log::debug!("Bundle {} {}", bundle_id, state),
+                WatchEvent::Finished(outcome) => return outcome,
+            }
+        }
+        BundleOutcome::TimedOut {
+            last_state: BundleState::Submitted,
+        }
+    }
+}